@@ -0,0 +1,46 @@
+pub mod systems;
+pub mod resourses;
+
+use bevy::prelude::*;
+use bevy::app::PluginGroupBuilder;
+use systems::actions::ActionsPlugin;
+use systems::autosave::AutosavePlugin;
+use systems::cli::CliPlugin;
+use systems::eventer::EventerPlugin;
+use systems::knockback::KnockbackPlugin;
+use systems::loader::ObjectsLoaderPlugin;
+use systems::monster::MonsterPlugin;
+use systems::physics::PhysicsPlugin;
+use systems::player::PlayerPlugin;
+use systems::player_game_ui::InventoryPlugin;
+use systems::save::SavePlugin;
+use systems::seasons::SeasonPlugin;
+use systems::settings::SettingsPlugin;
+use systems::structures::StructurePlugin;
+use systems::terrain::TerrainGenerationPlugin;
+use systems::tile_sim::TileSimPlugin;
+
+/// Logika gry - działa zarówno z RenderPlugins, jak i w trybie headless (także w testach)
+pub struct GamePlugins;
+
+impl PluginGroup for GamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(SettingsPlugin)
+            .add(ActionsPlugin)
+            .add(PhysicsPlugin)
+            .add(ObjectsLoaderPlugin)
+            .add(PlayerPlugin)
+            .add(InventoryPlugin)
+            .add(MonsterPlugin)
+            .add(TerrainGenerationPlugin)
+            .add(EventerPlugin)
+            .add(TileSimPlugin)
+            .add(SeasonPlugin)
+            .add(KnockbackPlugin)
+            .add(StructurePlugin)
+            .add(SavePlugin)
+            .add(AutosavePlugin)
+            .add(CliPlugin)
+    }
+}
@@ -1,39 +1,22 @@
-mod systems;
-mod resourses;
+use mgla_2d_game::{systems, GamePlugins};
 
 use bevy::prelude::*;
 use rapier2d::prelude::*;
-use resourses::physics_resources::*;
-use systems::menu_ui::MenuPlugin;
-use systems::player_game_ui::HudPlugin;
-use systems::monster::MonsterPlugin;
-use systems::physics::PhysicsPlugin;
-use systems::player::PlayerPlugin;
-use systems::terrain::TerrainGenerationPlugin;
-use systems::loader::ObjectsLoaderPlugin;
 //use bevy_light_2d::prelude::*;
-use bevy_firefly::prelude::*;
 use bevy_2d_screen_space_lightmaps::lightmap_plugin::lightmap_plugin::LightmapPlugin;
-use systems::eventer::EventerPlugin;
-use std::collections::HashMap;
+use systems::autosave::AutosavePlugin;
+use systems::settings::Settings;
+use systems::cli::{CliArgs, USAGE};
+use systems::save::set_user_data_dir;
+use systems::render::RenderPlugins;
+use systems::physics_debug::PhysicsDebugPlugin;
+use systems::headless::{HeadlessPlugin, HeadlessPlugins};
+use bevy::log::{Level, LogPlugin};
 
-use bevy::window::{WindowMode, MonitorSelection};
 use std::path::Path;
 
 use image::{DynamicImage, GenericImage, GenericImageView, ImageBuffer, Rgba};
 
-use std::fs;
-
-fn load_items_config(mut commands: Commands) {
-    let data = fs::read_to_string("assets/config/items.json")
-        .expect("Nie można wczytać pliku konfiguracyjnego");
-
-    let config: ItemConfig =
-        serde_json::from_str(&data).expect("Błąd parsowania pliku JSON");
-
-    commands.insert_resource(config);
-}
-
 fn main() {
     /*{
         let sprite1 = image::open("assets/textures/monster1.png").unwrap();
@@ -75,28 +58,52 @@ fn main() {
         // Zapisz nowy obrazek
         new_image.save("assets/textures/player_combined.png").unwrap();
     }*/
+    let cli = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli.help {
+        println!("{}", USAGE);
+        return;
+    }
+    if let Some(dir) = &cli.config_dir {
+        set_user_data_dir(dir.clone());
+    } else if cli.headless {
+        // symulacja nie może zostawiać slotów w zapisach gracza
+        set_user_data_dir(std::env::temp_dir().join("mgla_2d_game_headless"));
+    }
+    // ustawienia gracza (trudność, sterowanie) zmieniałyby wynik symulacji
+    let settings = if cli.headless { Settings::default() } else { Settings::load() };
+    let log = LogPlugin {
+        level: cli.log_level.unwrap_or(Level::INFO),
+        ..default()
+    };
+
     let mut app = App::new();
-    app.insert_resource(AtlasHandles(HashMap::new())).insert_resource(ClearColor(Color::NONE)).add_plugins((
-        DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                resolution: (1920_u32, 1080_u32).into(),
-                mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
+    app.insert_resource(ClearColor(Color::NONE));
+    if cli.headless {
+        app.add_plugins((HeadlessPlugins.set(HeadlessPlugin { ticks: cli.ticks }), log));
+    } else {
+        let mut window = settings.window();
+        cli.apply_to_window(&mut window);
+        app.add_plugins((
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(window),
                 ..default()
-            }),
-            ..default()
-        }).set(ImagePlugin::default_nearest()),
-        //Light2dPlugin,
-        //ScreenSpaceLightmapPlugin,
-        //LightmapPlugin,
-        FireflyPlugin,
-        MenuPlugin,
-        HudPlugin,
-        PhysicsPlugin,
-        ObjectsLoaderPlugin,
-        PlayerPlugin,
-        MonsterPlugin,
-        TerrainGenerationPlugin,
-        EventerPlugin,
-    )).add_systems(Startup, load_items_config);
+            }).set(ImagePlugin::default_nearest()).set(log),
+            //Light2dPlugin,
+            //ScreenSpaceLightmapPlugin,
+            //LightmapPlugin,
+            RenderPlugins.set(PhysicsDebugPlugin { hotkeys: cli.debug }),
+        ));
+    }
+    let game = GamePlugins.build();
+    // bez autozapisu i zapisu przy wyjściu - symulacja nie jest grą gracza
+    let game = if cli.headless { game.disable::<AutosavePlugin>() } else { game };
+    app.add_plugins(game)
+        .insert_resource(settings).insert_resource(cli.launch_options());
     app.run();
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use rapier2d::prelude::*;
-use serde::Deserialize;
+use rapier2d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Resource)]
@@ -39,20 +40,442 @@ pub struct ResCCDSolver(pub CCDSolver);
 #[derive(Resource)]
 pub struct ResQueryPipeline(pub QueryPipeline);
 
+/// Kolektor zdarzeń rapiera + odbiorniki kanałów, opróżniane po każdym kroku
+#[derive(Resource)]
+pub struct ResEventCollector {
+    pub collector: ChannelEventCollector,
+    pub collision_recv: rapier2d::crossbeam::channel::Receiver<CollisionEvent>,
+    pub contact_force_recv: rapier2d::crossbeam::channel::Receiver<ContactForceEvent>,
+}
+
+impl Default for ResEventCollector {
+    fn default() -> Self {
+        let (collision_send, collision_recv) = rapier2d::crossbeam::channel::unbounded();
+        let (contact_force_send, contact_force_recv) = rapier2d::crossbeam::channel::unbounded();
+        Self {
+            collector: ChannelEventCollector::new(collision_send, contact_force_send),
+            collision_recv,
+            contact_force_recv,
+        }
+    }
+}
+
+/// Początek kontaktu dwóch colliderów (lub wejście do sensora)
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CollisionStarted {
+    pub a: Entity,
+    pub b: Entity,
+    pub sensor: bool,
+}
+
+/// Koniec kontaktu dwóch colliderów (lub wyjście z sensora)
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CollisionStopped {
+    pub a: Entity,
+    pub b: Entity,
+    pub sensor: bool,
+}
+
+/// Siła kontaktu przekraczająca próg collidera
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ContactForce {
+    pub a: Entity,
+    pub b: Entity,
+    pub total_force_magnitude: f32,
+}
+
+/// Dwukierunkowe mapowanie encja <-> ciało/collidery rapiera
+#[derive(Resource, Default)]
+pub struct PhysicsEntityMap {
+    body_to_entity: HashMap<RigidBodyHandle, Entity>,
+    entity_to_body: HashMap<Entity, RigidBodyHandle>,
+    collider_to_entity: HashMap<ColliderHandle, Entity>,
+    entity_to_colliders: HashMap<Entity, Vec<ColliderHandle>>,
+}
+
+impl PhysicsEntityMap {
+    pub fn insert_body(&mut self, entity: Entity, body: RigidBodyHandle) {
+        self.body_to_entity.insert(body, entity);
+        self.entity_to_body.insert(entity, body);
+    }
+
+    pub fn insert_collider(&mut self, entity: Entity, collider: ColliderHandle) {
+        self.collider_to_entity.insert(collider, entity);
+        self.entity_to_colliders.entry(entity).or_default().push(collider);
+    }
+
+    pub fn entity_of_body(&self, body: RigidBodyHandle) -> Option<Entity> {
+        self.body_to_entity.get(&body).copied()
+    }
+
+    pub fn entity_of_collider(&self, collider: ColliderHandle) -> Option<Entity> {
+        self.collider_to_entity.get(&collider).copied()
+    }
+
+    pub fn body_of(&self, entity: Entity) -> Option<RigidBodyHandle> {
+        self.entity_to_body.get(&entity).copied()
+    }
+
+    pub fn colliders_of(&self, entity: Entity) -> &[ColliderHandle] {
+        self.entity_to_colliders.get(&entity).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// Usuwa wszystkie wpisy encji
+    pub fn remove_entity(&mut self, entity: Entity) {
+        if let Some(body) = self.entity_to_body.remove(&entity) {
+            self.body_to_entity.remove(&body);
+        }
+        if let Some(colliders) = self.entity_to_colliders.remove(&entity) {
+            for collider in colliders {
+                self.collider_to_entity.remove(&collider);
+            }
+        }
+    }
+
+    /// Encje, których ciała nie ma już w RigidBodySet
+    pub fn stale_entities(&self, bodies: &RigidBodySet) -> Vec<Entity> {
+        self.entity_to_body
+            .iter()
+            .filter(|(_, body)| !bodies.contains(**body))
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+}
+
 #[derive(Component)]
 pub struct RigidBodyHandleComponent(pub RigidBodyHandle);
 
+/// Poza ciała z dwóch ostatnich kroków fizyki - Transform jest między nimi interpolowany
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PhysicsPose {
+    pub previous: Vec2,
+    pub current: Vec2,
+    pub previous_rotation: f32,
+    pub current_rotation: f32,
+}
+
+impl PhysicsPose {
+    pub fn new(pos: Vec2, rotation: f32) -> Self {
+        Self {
+            previous: pos,
+            current: pos,
+            previous_rotation: rotation,
+            current_rotation: rotation,
+        }
+    }
+
+    /// Skok bez interpolacji (np. teleport)
+    pub fn teleport(&mut self, pos: Vec2, rotation: f32) {
+        *self = Self::new(pos, rotation);
+    }
+}
+
+/// Kontroler postaci dla kinematycznych ciał (gracz, potwory) - ruch wykonuje physics::move_characters.
+/// Długości w pikselach
+#[derive(Component, Clone, Copy, Debug)]
+#[require(CharacterMovement, CharacterOutput)]
+pub struct CharacterController {
+    /// szczelina zostawiana między postacią a otoczeniem
+    pub offset: f32,
+    /// ślizganie się wzdłuż ścian zamiast zatrzymania
+    pub slide: bool,
+    /// dociąganie do "podłoża" (kierunek -y), None = wyłączone
+    pub snap_to_ground: Option<f32>,
+    /// maks. wysokość progu pokonywanego automatycznie, None = wyłączone
+    pub step_height: Option<f32>,
+    /// min. wolne miejsce za progiem
+    pub step_min_width: f32,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            offset: 0.5,
+            slide: true,
+            snap_to_ground: None,
+            step_height: None,
+            step_min_width: 4.0,
+        }
+    }
+}
+
+impl CharacterController {
+    pub fn to_rapier(&self) -> KinematicCharacterController {
+        KinematicCharacterController {
+            offset: CharacterLength::Absolute(self.offset),
+            slide: self.slide,
+            snap_to_ground: self.snap_to_ground.map(CharacterLength::Absolute),
+            autostep: self.step_height.map(|height| CharacterAutostep {
+                max_height: CharacterLength::Absolute(height),
+                min_width: CharacterLength::Absolute(self.step_min_width),
+                include_dynamic_bodies: false,
+            }),
+            // widok z góry - każda ściana to ściana, bez zboczy
+            max_slope_climb_angle: std::f32::consts::FRAC_PI_2,
+            min_slope_slide_angle: std::f32::consts::FRAC_PI_2,
+            ..default()
+        }
+    }
+}
+
+/// Prędkość (px/s), z jaką gameplay chce przesunąć postać w tym kroku
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct CharacterMovement {
+    pub velocity: Vec2,
+}
+
+/// Wynik ostatniego ruchu postaci
+#[derive(Component, Clone, Default, Debug)]
+pub struct CharacterOutput {
+    /// faktyczne przesunięcie po uwzględnieniu kolizji
+    pub translation: Vec2,
+    pub grounded: bool,
+    /// encje, w które postać uderzyła
+    pub hits: Vec<Entity>,
+}
+
+/// Postać zatrzymała się na colliderze podczas ruchu
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CharacterCollided {
+    pub character: Entity,
+    pub other: Entity,
+    /// normalna powierzchni przeszkody (od przeszkody w stronę postaci)
+    pub normal: Vec2,
+}
+
+/// Masa i odporność na odrzut; bez komponentu: masa 1, odporność 0
+#[derive(Component, Clone, Copy, Debug)]
+pub struct KnockbackProps {
+    pub mass: f32,
+    /// 0 = pełny odrzut i ogłuszenie, 1 = brak
+    pub resistance: f32,
+}
+
+impl Default for KnockbackProps {
+    fn default() -> Self {
+        Self { mass: 1.0, resistance: 0.0 }
+    }
+}
+
+/// Prędkość odrzutu (px/s) dodawana do ruchu postaci, wygasa z czasem
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct Knockback(pub Vec2);
+
+/// Ogłuszenie - AI i sterowanie gracza nie działają do końca timera
+#[derive(Component, Debug)]
+pub struct Stunned(pub Timer);
+
+/// Żądanie odrzutu: impuls w kierunku direction + ogłuszenie na stun sekund
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ApplyKnockback {
+    pub target: Entity,
+    pub direction: Vec2,
+    pub impulse: f32,
+    pub stun: f32,
+}
+
+/// Encja rysowana dokładnie w pozycji ciała z ostatniego kroku, bez interpolacji
+#[derive(Component)]
+pub struct NoInterpolation;
+
+/// Sterowanie symulacją: skala czasu (Time<Virtual>) oraz debugowe zamrożenie z pracą krokową
+#[derive(Resource, Debug)]
+pub struct SimulationControl {
+    /// 1.0 = normalnie, < 1 zwolnienie, > 1 przyspieszenie
+    pub time_scale: f32,
+    /// zamrożenie niezależne od menu pauzy
+    pub frozen: bool,
+    /// kroki fizyki do wykonania w zamrożeniu
+    pub pending_steps: u32,
+}
+
+impl Default for SimulationControl {
+    fn default() -> Self {
+        Self {
+            time_scale: 1.0,
+            frozen: false,
+            pending_steps: 0,
+        }
+    }
+}
+
+/// Częstotliwość kroku fizyki (Time<Fixed> i dt rapiera)
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct PhysicsTimestep {
+    pub hz: f64,
+}
+
+impl Default for PhysicsTimestep {
+    fn default() -> Self {
+        Self { hz: 64.0 }
+    }
+}
+
 #[derive(Component)]
 pub struct ColliderComponent(pub ColliderHandle);
 
+/// Typ ciała rapiera, z którym encja ma zostać zbudowana (loader::inspect)
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PhysicsBody {
+    Dynamic,
+    Fixed,
+    Kinematic,
+}
+
+/// Kształt collidera w pikselach (przed skalą z Transform)
+#[derive(Component, Clone, Copy, Debug)]
+pub enum BodyShape {
+    Cuboid { half_extents: Vec2 },
+    Ball { radius: f32 },
+    Capsule { half_height: f32, radius: f32 },
+    /// trimesh wyliczony z Mesh2d encji
+    Trimesh,
+}
+
+impl BodyShape {
+    /// Kształt rapiera do zapytań (shape-cast, przecięcia); trimesh wymaga mesha, więc None
+    pub fn to_shared_shape(&self) -> Option<SharedShape> {
+        match *self {
+            BodyShape::Cuboid { half_extents } => Some(SharedShape::cuboid(half_extents.x, half_extents.y)),
+            BodyShape::Ball { radius } => Some(SharedShape::ball(radius)),
+            BodyShape::Capsule { half_height, radius } => Some(SharedShape::capsule_y(half_height, radius)),
+            BodyShape::Trimesh => None,
+        }
+    }
+}
+
+/// Materiał collidera - opcjonalny, domyślne wartości jak dawniej w loaderze
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ColliderProps {
+    pub friction: f32,
+    pub restitution: f32,
+    pub density: f32,
+    pub sensor: bool,
+}
+
+impl Default for ColliderProps {
+    fn default() -> Self {
+        Self {
+            friction: 0.5,
+            restitution: 0.0,
+            density: 1.0,
+            sensor: false,
+        }
+    }
+}
+
+/// Nazwana warstwa kolizji encji; brak komponentu = koliduje ze wszystkim
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionLayer {
+    Player,
+    Monster,
+    FlyingMonster,
+    Wall,
+    Water,
+    Projectile,
+    Sensor,
+    MonsterSensor,
+}
+
+impl CollisionLayer {
+    /// Bit grupy rapiera odpowiadający warstwie
+    pub fn group(self) -> Group {
+        match self {
+            CollisionLayer::Player => Group::GROUP_1,
+            CollisionLayer::Monster => Group::GROUP_2,
+            CollisionLayer::FlyingMonster => Group::GROUP_3,
+            CollisionLayer::Wall => Group::GROUP_4,
+            CollisionLayer::Water => Group::GROUP_5,
+            CollisionLayer::Projectile => Group::GROUP_6,
+            CollisionLayer::Sensor => Group::GROUP_7,
+            CollisionLayer::MonsterSensor => Group::GROUP_8,
+        }
+    }
+}
+
+/// Centralna tabela warstw (assets/config/collision_layers.json):
+/// warstwa -> lista warstw, z którymi koliduje.
+/// Rapier wymaga zgody obu stron, więc tabela powinna być symetryczna
+#[derive(Resource, Debug, Deserialize)]
+pub struct CollisionLayerConfig {
+    pub layers: HashMap<CollisionLayer, Vec<CollisionLayer>>,
+}
+
+impl CollisionLayerConfig {
+    pub fn groups(&self, layer: CollisionLayer) -> InteractionGroups {
+        let filter = self
+            .layers
+            .get(&layer)
+            .map(|others| others.iter().fold(Group::NONE, |acc, other| acc | other.group()))
+            .unwrap_or(Group::NONE);
+        InteractionGroups::new(layer.group(), filter)
+    }
+}
+
+/// Właściciel pocisku - para pocisk/właściciel jest pomijana przez hooki fizyki
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ProjectileOwner(pub Entity);
+
+/// Pociski i ich właściciele (encja pocisku -> encja właściciela)
+#[derive(Resource, Default)]
+pub struct ProjectileOwners(pub HashMap<Entity, Entity>);
+
+/// Hooki rapiera odfiltrowujące kontakty pocisku z jego właścicielem
+pub struct OwnerFilterHooks<'a> {
+    pub owners: &'a HashMap<Entity, Entity>,
+    pub entity_map: &'a PhysicsEntityMap,
+}
+
+impl OwnerFilterHooks<'_> {
+    fn ignores(&self, context: &PairFilterContext) -> bool {
+        let entity_of = |handle: ColliderHandle| self.entity_map.entity_of_collider(handle);
+        let (Some(a), Some(b)) = (entity_of(context.collider1), entity_of(context.collider2)) else {
+            return false;
+        };
+        self.owners.get(&a) == Some(&b) || self.owners.get(&b) == Some(&a)
+    }
+}
+
+impl PhysicsHooks for OwnerFilterHooks<'_> {
+    fn filter_contact_pair(&self, context: &PairFilterContext) -> Option<SolverFlags> {
+        if self.ignores(context) {
+            None
+        } else {
+            Some(SolverFlags::COMPUTE_IMPULSES)
+        }
+    }
+
+    fn filter_intersection_pair(&self, context: &PairFilterContext) -> bool {
+        !self.ignores(context)
+    }
+}
+
 #[derive(Resource)]
 pub struct ResPhysicsWork(pub bool);
 
-#[derive(Resource)]
-pub struct GameStatus(pub bool);
+/// Stan gry - systemy rozgrywki działają tylko w Playing
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    /// wczytywanie zasobów menu
+    #[default]
+    Loading,
+    MainMenu,
+    Playing,
+    Paused,
+    GameOver,
+}
 
-#[derive(Resource)]
-pub struct ResumeStatus(pub bool);
+/// Ekran menu głównego i menu pauzy
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(AppState = AppState::MainMenu | AppState::Paused)]
+pub enum MenuScreen {
+    #[default]
+    Main,
+    SaveSlots,
+    Options,
+    Controls,
+}
 
 #[derive(Component)]
 pub struct AttackStatus(pub bool);
@@ -61,7 +484,11 @@ pub struct AttackStatus(pub bool);
 pub struct FinishStatus(pub bool);
 
 #[derive(Component)]
-pub struct PointText(pub u32);
+pub struct PointText;
+
+/// Punkty za zabite potwory - zapisywane razem z grą
+#[derive(Resource, Default)]
+pub struct Score(pub u32);
 
 #[derive(Component, Clone)]
 pub struct AnimationIndices {
@@ -69,7 +496,7 @@ pub struct AnimationIndices {
     pub last: usize,
 }
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct AtlasHandles (
     pub HashMap<String, AnimationIndices>,
 );
@@ -84,6 +511,7 @@ pub struct Player;
 pub struct PlayerUIs;
 
 #[derive(Component)]
+#[require(HazardTint)]
 pub struct PlayerSprite;
 
 #[derive(Component)]
@@ -92,6 +520,20 @@ pub struct WaterSprite;
 #[derive(Component)]
 pub struct Floor;
 
+/// Sprite kafelka podłogi (dziecko Floor obok światła, occludera i ściany klifu)
+#[derive(Component)]
+pub struct FloorSprite;
+
+#[derive(Component)]
+pub struct Water;
+
+/// Lód przykrywający wodę zimą
+#[derive(Component)]
+pub struct IceSprite;
+
+#[derive(Component)]
+pub struct Frozen;
+
 #[derive(Component)]
 pub struct Fog;
 
@@ -101,6 +543,10 @@ pub struct FogHalo;
 #[derive(Component)]
 pub struct Wall;
 
+/// Krawędź wyższego poziomu terenu (klif) - blokuje przejście między poziomami
+#[derive(Component)]
+pub struct Cliff;
+
 #[derive(Component)]
 pub struct Pending;
 
@@ -108,6 +554,7 @@ pub struct Pending;
 pub struct Monster;
 
 #[derive(Component)]
+#[require(HazardTint)]
 pub struct MonsterSprite;
 
 #[derive(Debug, Deserialize, Resource)]
@@ -122,6 +569,12 @@ pub struct Item {
     pub value: [f32; 2],
     pub item_type: String,
     pub amount: u32,
+    /// impuls odrzutu zadawany trafionej encji (broń)
+    #[serde(default)]
+    pub knockback: f32,
+    /// czas ogłuszenia trafionej encji w sekundach (broń)
+    #[serde(default)]
+    pub stun: f32,
 }
 
 pub struct Inventory {
@@ -157,6 +610,14 @@ impl Inventory {
         }
     }
 
+    /// Wkłada przedmiot do pierwszego wolnego slotu
+    pub fn add_to_free_slot(&mut self, item: Item) -> bool {
+        match (0..self.capacity).find(|slot| !self.items.contains_key(slot)) {
+            Some(slot) => self.add_item(slot, item),
+            None => false,
+        }
+    }
+
     pub fn remove_item(&mut self, slot: u32) -> Option<Item> {
         self.items.remove(&slot)
     }
@@ -172,6 +633,8 @@ impl Inventory {
                     value: item.value,
                     item_type: item.item_type.clone(),
                     amount: 1, // zwracamy tylko tę jedną sztukę
+                    knockback: item.knockback,
+                    stun: item.stun,
                 })
             } else {
                 // amount == 1, więc usuwamy całkowicie
@@ -185,6 +648,274 @@ impl Inventory {
     pub fn get_item(&self, slot: u32) -> Option<&Item> {
         self.items.get(&slot)
     }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.items.values().any(|item| item.id == id)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Biome {
+    Normal,
+    Snow,
+    Evil,
+}
+
+/// Rodzaje niebezpiecznych kafelków podłogi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HazardKind {
+    PoisonSwamp,
+    Lava,
+    Thorns,
+}
+
+impl HazardKind {
+    pub fn damage_per_second(self) -> f32 {
+        match self {
+            HazardKind::PoisonSwamp => 4.0,
+            HazardKind::Lava => 25.0,
+            HazardKind::Thorns => 6.0,
+        }
+    }
+
+    /// Mnożnik prędkości ruchu na kafelku
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            HazardKind::PoisonSwamp => 0.6,
+            HazardKind::Lava => 1.0,
+            HazardKind::Thorns => 0.75,
+        }
+    }
+
+    /// Kolor kafelka oraz podświetlenia encji, która na nim stoi
+    pub fn tint(self) -> Color {
+        match self {
+            HazardKind::PoisonSwamp => Color::srgb(0.45, 0.85, 0.3),
+            HazardKind::Lava => Color::srgb(1.0, 0.45, 0.15),
+            HazardKind::Thorns => Color::srgb(0.7, 0.55, 0.4),
+        }
+    }
+}
+
+/// Zagrożenie, którym sprite postaci jest aktualnie podbarwiony (kolor zmieniamy tylko przy zmianie)
+#[derive(Component, Default)]
+pub struct HazardTint(pub Option<HazardKind>);
+
+/// Stan kafelka zmieniany przez symulację (ogień, odrastanie trawy, śnieg)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileState {
+    Grass,
+    Burning { ticks: u32 },
+    Burned { ticks: u32 },
+    Snowy,
+}
+
+/// Seed świata - z niego pochodzą wszystkie noise terenu i losowania symulacji.
+/// Seed 0 daje świat taki sam jak przed wprowadzeniem seedów
+#[derive(Resource, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct WorldSeed(pub u64);
+
+impl WorldSeed {
+    /// Seed pojedynczego noise (salt odróżnia warstwy terenu)
+    pub fn noise_seed(&self, salt: u32) -> u32 {
+        ((self.0 ^ (self.0 >> 32)) as u32) ^ salt
+    }
+
+    /// Seed losowań symulacji i struktur
+    pub fn sim_seed(&self, salt: u64) -> u64 {
+        self.0 ^ salt
+    }
+}
+
+/// Zmiany świata względem tego, co generuje noise - przeżywają wyładowanie terenu
+#[derive(Resource, Default)]
+pub struct WorldDiff {
+    pub tiles: HashMap<IVec2, TileState>,
+}
+
+#[derive(Resource)]
+pub struct Weather {
+    pub cold: bool, // śnieg zasypuje trawę, woda zamarza
+    pub grass_tint: Color, // paleta trawy zależna od pory roku
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            cold: false,
+            grass_tint: Color::WHITE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Season {
+    Summer,
+    Autumn,
+    Winter,
+    Spring,
+}
+
+impl Season {
+    /// Jaka część doby jest jasna
+    pub fn daylight(self) -> f32 {
+        match self {
+            Season::Summer => 0.7,
+            Season::Autumn => 0.5,
+            Season::Winter => 0.35,
+            Season::Spring => 0.55,
+        }
+    }
+
+    pub fn is_cold(self) -> bool {
+        self == Season::Winter
+    }
+
+    pub fn grass_tint(self) -> Color {
+        match self {
+            Season::Summer => Color::WHITE,
+            Season::Autumn => Color::srgb(1.0, 0.78, 0.5),
+            Season::Winter => Color::srgb(0.85, 0.9, 1.0),
+            Season::Spring => Color::srgb(0.9, 1.0, 0.9),
+        }
+    }
+
+    /// Dodatkowe potwory ponad bazowy limit z MonsterConfig
+    pub fn extra_monsters(self) -> usize {
+        match self {
+            Season::Summer | Season::Spring => 0,
+            Season::Autumn => 1,
+            Season::Winter => 2,
+        }
+    }
+}
+
+pub const DAY_SECONDS: f32 = 240.0;
+pub const DAYS_PER_SEASON: u32 = 3;
+
+/// Kalendarz gry - zapisywany razem ze światem
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct GameClock {
+    pub day: u32,
+    pub time_of_day: f32, // 0.0 = północ, 0.5 = południe
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            day: 0,
+            time_of_day: 0.3,
+        }
+    }
+}
+
+impl GameClock {
+    pub fn season(&self) -> Season {
+        match (self.day / DAYS_PER_SEASON) % 4 {
+            0 => Season::Summer,
+            1 => Season::Autumn,
+            2 => Season::Winter,
+            _ => Season::Spring,
+        }
+    }
+
+    /// Jasność dnia 0..1 (0 = noc), długość dnia zależy od pory roku
+    pub fn daylight(&self) -> f32 {
+        let half_day = self.season().daylight() * 0.5;
+        let from_noon = (self.time_of_day - 0.5).abs();
+        ((half_day - from_noon) / 0.05).clamp(0.0, 1.0)
+    }
+}
+
+/// Efekt środowiskowy biomu działający na gracza (z assets/config/biomes.json)
+#[derive(Debug, Deserialize, Clone)]
+pub struct BiomeEffect {
+    pub name: String,
+    #[serde(default)]
+    pub stamina_drain: f32, // staminy na sekundę
+    #[serde(default)]
+    pub damage_per_second: f32,
+    #[serde(default)]
+    pub protective_item: Option<String>, // przedmiot w ekwipunku, który chroni przed efektem
+}
+
+#[derive(Debug, Deserialize, Resource)]
+pub struct BiomeConfig {
+    pub biomes: HashMap<Biome, BiomeEffect>,
+}
+
+/// Efekt strefy wyzwalanej
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ZoneEffect {
+    /// leczy gracza w środku (np. ognisko)
+    Heal { per_second: f32 },
+    /// rani i ogłusza wchodzącego
+    Trap { damage: f32, stun: f32 },
+    /// odkrycie miejsca przy pierwszym wejściu gracza
+    Discovery { name: String },
+    /// przedmiot do wzięcia akcją Interact, jeśli gracz jeszcze go nie ma
+    Reward { item: String },
+}
+
+/// Strefa oparta na sensorze rapiera; wejścia/wyjścia jako ZoneEntered/ZoneExited
+#[derive(Component, Debug, Clone)]
+#[require(ZoneOccupants)]
+pub struct TriggerZone {
+    pub effect: ZoneEffect,
+}
+
+/// Encje aktualnie znajdujące się w strefie
+#[derive(Component, Debug, Default)]
+pub struct ZoneOccupants(pub HashSet<Entity>);
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ZoneEntered {
+    pub zone: Entity,
+    pub entity: Entity,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ZoneExited {
+    pub zone: Entity,
+    pub entity: Entity,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StructureLight {
+    pub range: f32,
+    pub intensity: f32,
+    pub color: [f32; 3],
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ZoneTemplate {
+    /// przesunięcie względem środka struktury (px)
+    #[serde(default)]
+    pub offset: [f32; 2],
+    pub radius: f32,
+    pub effect: ZoneEffect,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StructureTemplate {
+    pub texture: String,
+    /// rozmiar sprite'a (px)
+    pub size: f32,
+    /// szansa na kafelek podłogi
+    pub chance: f32,
+    pub biomes: Vec<Biome>,
+    #[serde(default)]
+    pub light: Option<StructureLight>,
+    #[serde(default)]
+    pub zones: Vec<ZoneTemplate>,
+}
+
+/// Szablony struktur (assets/config/structures.json)
+#[derive(Debug, Deserialize, Resource)]
+pub struct StructureConfig {
+    pub structures: HashMap<String, StructureTemplate>,
 }
 
 #[derive(Component)]
@@ -277,6 +1008,14 @@ pub struct YSort {
     pub z: f32,
 }
 
+/// Źródło światła; PointLight2d dokłada dopiero część renderująca
+#[derive(Component, Clone, Copy)]
+pub struct LightSource {
+    pub range: f32,
+    pub intensity: f32,
+    pub color: Color,
+}
+
 #[derive(Resource)]
 pub struct InventoryState {
     pub selected: usize, // aktualnie wybrany slot
@@ -291,27 +1030,57 @@ pub struct InventoryImage(pub String);
 
 pub const WORLD_SIZE: i32 = 96;   // liczba kafelków widocznych w danym "obszarze"
 pub const TILE_SIZE: f32 = 64.0;
+pub const MAX_ELEVATION: i32 = 2; // najwyższy poziom terenu (0 = poziom morza)
 
 #[derive(Component)]
 pub struct MonsterAI {
     pub target_player: bool,
+    /// gracz w zasięgu wzroku i bez ściany pomiędzy (monster_sight)
+    pub sees_player: bool,
     pub random_timer: Timer,
     pub random_dir: Vec2,
     pub action_timer: Timer,
     pub action_cooldown: Timer,
     pub health: f32,
-    pub last_health: f32,
-    pub stun_cooldown: Timer,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum MenuButtonAction {
     NewGame,
     LoadGame,
+    SaveGame,
+    Resume,
     Options,
     Exit,
 }
 
+/// Nowa gra od zera - bez seeda losowany jest nowy świat, bez slotu zajmowany jest wolny slot
+#[derive(Message)]
+pub struct NewGame {
+    pub seed: Option<u64>,
+    pub slot: Option<String>,
+}
+
+/// Zapis bieżącej gry do slotu
+#[derive(Message)]
+pub struct SaveGame {
+    pub slot: String,
+}
+
+/// Gra została zapisana (HUD pokazuje wskaźnik zapisu)
+#[derive(Message)]
+pub struct GameSaved {
+    pub slot: String,
+}
+
+/// Wczytanie gry ze slotu (zastępuje bieżący świat)
+#[derive(Message)]
+pub struct LoadGame {
+    pub slot: String,
+    /// szybki odczyt: gra dalej zapisuje do bieżącego slotu, a nie do wczytanego
+    pub keep_slot: bool,
+}
+
 #[derive(Component, Clone, Copy)]
 pub struct MenuButton(pub MenuButtonAction);
 
@@ -342,5 +1111,12 @@ pub struct HealthBar;
 #[derive(Component)]
 pub struct SataminaBar;
 
+#[derive(Component)]
+pub struct EnvironmentIndicator;
+
+/// Napis "Saving..." widoczny chwilę po zapisie gry
+#[derive(Component)]
+pub struct SavingIndicator(pub Timer);
+
 #[derive(Component)]
 pub struct DebugAI;
\ No newline at end of file
@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+use bevy::input::InputSystems;
+use bevy::window::PrimaryWindow;
+use crate::systems::settings::Settings;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub struct ActionsPlugin;
+
+/// od tej wartości akcja przyciskowa jest wciśnięta (ważne dla gałek)
+const PRESS_THRESHOLD: f32 = 0.5;
+pub const HOTBAR_SLOTS: u8 = 10;
+
+/// Akcje gry niezależne od urządzenia wejścia
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveX,
+    MoveY,
+    Sprint,
+    Attack,
+    UseItem,
+    NextSlot,
+    Hotbar(u8),
+    Pause,
+    Interact,
+    QuickSave,
+    QuickLoad,
+    DebugOverlay,
+    PhysicsFreeze,
+    PhysicsStep,
+    PhysicsSlower,
+    PhysicsFaster,
+    PhysicsResetSpeed,
+}
+
+/// Fizyczne wejście przypisane do akcji
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum InputSource {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    GamepadButton(GamepadButton),
+    GamepadAxis(GamepadAxis),
+}
+
+impl InputSource {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, InputSource::GamepadButton(_) | InputSource::GamepadAxis(_))
+    }
+
+    /// Krótka nazwa do menu sterowania
+    pub fn name(&self) -> String {
+        let name = match self {
+            InputSource::Key(key) => format!("{:?}", key),
+            InputSource::Mouse(button) => format!("Mouse {:?}", button),
+            InputSource::GamepadButton(button) => format!("Pad {:?}", button),
+            InputSource::GamepadAxis(axis) => format!("Pad {:?}", axis),
+        };
+        name.replace("Key", "").replace("Digit", "")
+    }
+}
+
+/// Przypisanie: wartość wejścia * scale trafia do akcji (scale -1 = kierunek ujemny osi)
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct Binding {
+    pub action: Action,
+    pub input: InputSource,
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// odwrócona oś pada - wiersz (znak scale) zostaje ten sam
+    #[serde(default)]
+    pub invert: bool,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl Binding {
+    fn new(action: Action, input: InputSource) -> Self {
+        Self { action, input, scale: 1.0, invert: false }
+    }
+
+    fn negative(action: Action, input: InputSource) -> Self {
+        Self { action, input, scale: -1.0, invert: false }
+    }
+
+    /// Mnożnik wartości wejścia
+    pub fn factor(&self) -> f32 {
+        if self.invert { -self.scale } else { self.scale }
+    }
+}
+
+/// Lista przypisań zapisywana w ustawieniach
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(transparent)]
+pub struct InputMap(pub Vec<Binding>);
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use InputSource::{Key, Mouse};
+        let pad = InputSource::GamepadButton;
+        let stick = InputSource::GamepadAxis;
+        let mut bindings = vec![
+            Binding::negative(Action::MoveX, Key(KeyCode::KeyA)),
+            Binding::new(Action::MoveX, Key(KeyCode::KeyD)),
+            Binding::negative(Action::MoveX, pad(GamepadButton::DPadLeft)),
+            Binding::new(Action::MoveX, pad(GamepadButton::DPadRight)),
+            Binding::new(Action::MoveX, stick(GamepadAxis::LeftStickX)),
+            Binding::new(Action::MoveY, Key(KeyCode::KeyW)),
+            Binding::negative(Action::MoveY, Key(KeyCode::KeyS)),
+            Binding::new(Action::MoveY, pad(GamepadButton::DPadUp)),
+            Binding::negative(Action::MoveY, pad(GamepadButton::DPadDown)),
+            Binding::new(Action::MoveY, stick(GamepadAxis::LeftStickY)),
+            Binding::new(Action::Sprint, Key(KeyCode::ShiftLeft)),
+            Binding::new(Action::Sprint, pad(GamepadButton::LeftThumb)),
+            Binding::new(Action::Attack, Mouse(MouseButton::Left)),
+            Binding::new(Action::Attack, pad(GamepadButton::RightTrigger2)),
+            Binding::new(Action::UseItem, Mouse(MouseButton::Left)),
+            Binding::new(Action::UseItem, pad(GamepadButton::West)),
+            Binding::new(Action::NextSlot, Key(KeyCode::Tab)),
+            Binding::new(Action::NextSlot, pad(GamepadButton::RightTrigger)),
+            Binding::new(Action::Pause, Key(KeyCode::Escape)),
+            Binding::new(Action::Pause, pad(GamepadButton::Start)),
+            Binding::new(Action::Interact, Key(KeyCode::KeyE)),
+            Binding::new(Action::Interact, pad(GamepadButton::South)),
+            Binding::new(Action::QuickSave, Key(KeyCode::F5)),
+            Binding::new(Action::QuickLoad, Key(KeyCode::F9)),
+            Binding::new(Action::DebugOverlay, Key(KeyCode::F3)),
+            Binding::new(Action::PhysicsFreeze, Key(KeyCode::F6)),
+            Binding::new(Action::PhysicsStep, Key(KeyCode::F7)),
+            Binding::new(Action::PhysicsSlower, Key(KeyCode::BracketLeft)),
+            Binding::new(Action::PhysicsFaster, Key(KeyCode::BracketRight)),
+            Binding::new(Action::PhysicsResetSpeed, Key(KeyCode::Backslash)),
+        ];
+        let digits = [
+            KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4, KeyCode::Digit5,
+            KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9, KeyCode::Digit0,
+        ];
+        for (slot, key) in (0..HOTBAR_SLOTS).zip(digits) {
+            bindings.push(Binding::new(Action::Hotbar(slot), Key(key)));
+        }
+        Self(bindings)
+    }
+}
+
+impl InputMap {
+    /// Akcje bez żadnego przypisania (np. dodane w nowszej wersji) dostają domyślne
+    pub fn fill_missing(&mut self) {
+        let bound: HashSet<Action> = self.0.iter().map(|binding| binding.action).collect();
+        let defaults = InputMap::default().0;
+        self.0.extend(defaults.into_iter().filter(|binding| !bound.contains(&binding.action)));
+    }
+
+    /// Przypisania akcji w danym kierunku (znak scale)
+    pub fn bindings_for(&self, action: Action, scale: f32) -> impl Iterator<Item = &Binding> {
+        self.0.iter().filter(move |binding| binding.action == action && binding.scale.signum() == scale.signum())
+    }
+
+    /// Zamiana przypisania w danym kierunku - osobno klawiatura/mysz i pad
+    pub fn rebind(&mut self, direction: f32, new: Binding) {
+        self.0.retain(|binding| {
+            binding.action != new.action
+                || binding.scale.signum() != direction.signum()
+                || binding.input.is_gamepad() != new.input.is_gamepad()
+        });
+        self.0.push(new);
+    }
+}
+
+/// Stan akcji w bieżącej klatce
+#[derive(Resource, Default)]
+pub struct ActionState {
+    values: HashMap<Action, f32>,
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    aim: Option<Vec2>,
+}
+
+impl ActionState {
+    pub fn value(&self, action: Action) -> f32 {
+        self.values.get(&action).copied().unwrap_or(0.0)
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Kierunek ruchu z osi MoveX/MoveY (długość do 1)
+    pub fn movement(&self) -> Vec2 {
+        Vec2::new(self.value(Action::MoveX), self.value(Action::MoveY)).clamp_length_max(1.0)
+    }
+
+    /// Kierunek od środka ekranu do kursora (y w górę); brak okna albo kursora = None
+    pub fn aim(&self) -> Option<Vec2> {
+        self.aim
+    }
+
+    /// Pochłania wciśnięcia z tej klatki - np. podczas przypisywania klawisza w menu
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+    }
+}
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionState>()
+            .add_systems(PreUpdate, update_actions.after(InputSystems));
+    }
+}
+
+fn source_value(
+    input: InputSource,
+    keyboard: &ButtonInput<KeyCode>,
+    mouse: &ButtonInput<MouseButton>,
+    gamepads: &Query<&Gamepad>,
+) -> f32 {
+    let pressed = |down: bool| if down { 1.0 } else { 0.0 };
+    match input {
+        InputSource::Key(key) => pressed(keyboard.pressed(key)),
+        InputSource::Mouse(button) => pressed(mouse.pressed(button)),
+        InputSource::GamepadButton(button) => pressed(gamepads.iter().any(|gamepad| gamepad.pressed(button))),
+        InputSource::GamepadAxis(axis) => gamepads.iter()
+            .filter_map(|gamepad| gamepad.get(axis))
+            .fold(0.0, |best: f32, value| if value.abs() > best.abs() { value } else { best }),
+    }
+}
+
+/// Przeliczenie przypisań na wartości akcji
+pub fn update_actions(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+    mut actions: ResMut<ActionState>,
+) {
+    let mut values: HashMap<Action, f32> = HashMap::new();
+    for binding in &settings.controls.0 {
+        let value = source_value(binding.input, &keyboard, &mouse, &gamepads) * binding.factor();
+        *values.entry(binding.action).or_default() += value;
+    }
+    for value in values.values_mut() {
+        *value = value.clamp(-1.0, 1.0);
+    }
+
+    let pressed: HashSet<Action> = values.iter()
+        .filter(|(_, value)| value.abs() >= PRESS_THRESHOLD)
+        .map(|(action, _)| *action)
+        .collect();
+    actions.just_pressed = pressed.difference(&actions.pressed).copied().collect();
+    actions.pressed = pressed;
+    actions.values = values;
+    actions.aim = windows.single().ok().and_then(|window| {
+        let cursor = window.cursor_position()?;
+        Some(Vec2::new(cursor.x - window.width() / 2.0, window.height() / 2.0 - cursor.y).normalize_or_zero())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverted_axis_stays_on_requested_row() {
+        let mut map = InputMap::default();
+        let stick = InputSource::GamepadAxis(GamepadAxis::LeftStickY);
+        map.rebind(1.0, Binding { action: Action::MoveY, input: stick, scale: 1.0, invert: true });
+
+        let up: Vec<&Binding> = map.bindings_for(Action::MoveY, 1.0).filter(|b| b.input.is_gamepad()).collect();
+        assert_eq!(up.len(), 1);
+        assert_eq!(up[0].input, stick);
+        assert_eq!(up[0].factor(), -1.0);
+        assert!(map.bindings_for(Action::MoveY, -1.0).all(|b| b.input != stick));
+    }
+
+    #[test]
+    fn bindings_without_invert_still_load() {
+        let binding: Binding = serde_json::from_str(r#"{ "action": "move_x", "input": { "key": "KeyA" }, "scale": -1.0 }"#).unwrap();
+        assert!(!binding.invert);
+        assert_eq!(binding.factor(), -1.0);
+    }
+}
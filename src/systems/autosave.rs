@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy::app::AppExit;
+use crate::resourses::physics_resources::*;
+use crate::systems::actions::{Action, ActionState};
+use crate::systems::save::{CurrentSlot, GameSnapshot, SaveDir};
+
+pub struct AutosavePlugin;
+
+pub const QUICKSAVE_SLOT: &str = "quicksave";
+const AUTOSAVE_SECONDS: f32 = 120.0;
+
+/// Odliczanie do autozapisu; due = zapis czeka na koniec walki
+#[derive(Resource)]
+pub(crate) struct Autosave {
+    timer: Timer,
+    due: bool,
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(AUTOSAVE_SECONDS, TimerMode::Repeating),
+            due: false,
+        }
+    }
+}
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Autosave>()
+            .add_systems(Update, (autosave, quicksave_keys).run_if(in_state(AppState::Playing)))
+            .add_systems(Last, save_on_exit.run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))));
+    }
+}
+
+/// Walka = któryś potwór goni gracza
+fn in_combat(monsters: &Query<&MonsterAI, With<Monster>>) -> bool {
+    monsters.iter().any(|ai| ai.target_player)
+}
+
+fn autosave(
+    time: Res<Time>,
+    mut state: ResMut<Autosave>,
+    current_slot: Res<CurrentSlot>,
+    monsters: Query<&MonsterAI, With<Monster>>,
+    mut save_game: MessageWriter<SaveGame>,
+) {
+    if state.timer.tick(time.delta()).just_finished() {
+        state.due = true;
+    }
+    if state.due && !in_combat(&monsters) {
+        state.due = false;
+        save_game.write(SaveGame { slot: current_slot.0.clone() });
+    }
+}
+
+fn quicksave_keys(
+    actions: Res<ActionState>,
+    save_dir: Res<SaveDir>,
+    mut save_game: MessageWriter<SaveGame>,
+    mut load_game: MessageWriter<LoadGame>,
+) {
+    if actions.just_pressed(Action::QuickSave) {
+        save_game.write(SaveGame { slot: QUICKSAVE_SLOT.to_string() });
+    }
+    if actions.just_pressed(Action::QuickLoad) {
+        if save_dir.slot_path(QUICKSAVE_SLOT).exists() {
+            load_game.write(LoadGame { slot: QUICKSAVE_SLOT.to_string(), keep_slot: true });
+        } else {
+            info!("Brak szybkiego zapisu");
+        }
+    }
+}
+
+/// Zapis przy normalnym wyjściu - od razu, bo po tej klatce aplikacja się kończy
+fn save_on_exit(
+    mut exits: MessageReader<AppExit>,
+    save_dir: Res<SaveDir>,
+    current_slot: Res<CurrentSlot>,
+    snapshot: GameSnapshot,
+) {
+    if exits.read().last().is_none() {
+        return;
+    }
+    if let Some(save) = snapshot.capture() {
+        save_dir.write_slot(&current_slot.0, &save);
+    }
+}
@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use bevy::log::Level;
+use crate::resourses::physics_resources::*;
+use std::path::PathBuf;
+
+pub struct CliPlugin;
+
+pub const USAGE: &str = "\
+Użycie: mgla_2d_game [OPCJE]
+
+  --seed <N>             seed nowego świata
+  --windowed             gra w oknie
+  --resolution <WxH>     rozdzielczość okna, np. 1280x720
+  --load <slot>          wczytanie slotu zapisu
+  --skip-menu            od razu nowa gra (albo --load) bez menu głównego
+  --log-level <poziom>   trace, debug, info, warn albo error
+  --config-dir <katalog> katalog ustawień i zapisów
+  --headless             symulacja bez okna i GPU (od razu gra)
+  --ticks <N>            koniec po N krokach fizyki (tylko z --headless)
+  --debug                skróty debugowania fizyki (zamrożenie, krok, tempo)
+  -h, --help             ta pomoc";
+
+/// Argumenty linii poleceń
+#[derive(Default, Debug)]
+pub struct CliArgs {
+    pub seed: Option<u64>,
+    pub windowed: bool,
+    pub resolution: Option<[u32; 2]>,
+    pub load: Option<String>,
+    pub skip_menu: bool,
+    pub log_level: Option<Level>,
+    pub config_dir: Option<PathBuf>,
+    pub headless: bool,
+    pub ticks: Option<u32>,
+    pub debug: bool,
+    pub help: bool,
+}
+
+/// Co zrobić po załadowaniu zasobów - zamiast menu od razu gra
+#[derive(Resource, Default)]
+pub struct LaunchOptions {
+    pub seed: Option<u64>,
+    pub load: Option<String>,
+    pub skip_menu: bool,
+}
+
+impl CliArgs {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // --opcja=wartość albo --opcja wartość
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let mut value = || inline.clone().or_else(|| args.next()).ok_or(format!("{} wymaga wartości", flag));
+            match flag.as_str() {
+                "--seed" => {
+                    let seed = value()?;
+                    cli.seed = Some(seed.parse().map_err(|_| format!("Błędny seed: {}", seed))?);
+                }
+                "--windowed" => cli.windowed = true,
+                "--resolution" => cli.resolution = Some(parse_resolution(&value()?)?),
+                "--load" => cli.load = Some(value()?),
+                "--skip-menu" => cli.skip_menu = true,
+                "--log-level" => {
+                    let level = value()?;
+                    cli.log_level = Some(level.parse().map_err(|_| format!("Błędny poziom logów: {}", level))?);
+                }
+                "--config-dir" => cli.config_dir = Some(PathBuf::from(value()?)),
+                "--headless" => cli.headless = true,
+                "--ticks" => {
+                    let ticks = value()?;
+                    cli.ticks = Some(ticks.parse().map_err(|_| format!("Błędna liczba ticków: {}", ticks))?);
+                }
+                "--debug" => cli.debug = true,
+                "-h" | "--help" => cli.help = true,
+                _ => return Err(format!("Nieznana opcja: {}", flag)),
+            }
+        }
+        if cli.ticks.is_some() && !cli.headless {
+            return Err("--ticks działa tylko z --headless".to_string());
+        }
+        Ok(cli)
+    }
+
+    /// Okno z ustawień nadpisane opcjami (bez zapisywania ich w ustawieniach)
+    pub fn apply_to_window(&self, window: &mut Window) {
+        if self.windowed {
+            window.mode = bevy::window::WindowMode::Windowed;
+        }
+        if let Some([width, height]) = self.resolution {
+            window.resolution.set_physical_resolution(width, height);
+        }
+    }
+
+    pub fn launch_options(&self) -> LaunchOptions {
+        LaunchOptions {
+            seed: self.seed,
+            load: self.load.clone(),
+            // headless nie ma menu
+            skip_menu: self.skip_menu || self.headless,
+        }
+    }
+}
+
+fn parse_resolution(text: &str) -> Result<[u32; 2], String> {
+    let error = || format!("Błędna rozdzielczość: {} (oczekiwano np. 1280x720)", text);
+    let (width, height) = text.split_once(['x', 'X']).ok_or_else(error)?;
+    let width: u32 = width.parse().map_err(|_| error())?;
+    let height: u32 = height.parse().map_err(|_| error())?;
+    if width == 0 || height == 0 {
+        return Err(error());
+    }
+    Ok([width, height])
+}
+
+impl Plugin for CliPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaunchOptions>()
+            .add_systems(OnEnter(AppState::MainMenu), launch_from_cli);
+    }
+}
+
+/// Jednorazowo po pierwszym wejściu do menu: --load albo --skip-menu
+fn launch_from_cli(
+    mut launch: ResMut<LaunchOptions>,
+    mut new_game: MessageWriter<NewGame>,
+    mut load_game: MessageWriter<LoadGame>,
+) {
+    if let Some(slot) = launch.load.take() {
+        load_game.write(LoadGame { slot, keep_slot: false });
+    } else if launch.skip_menu {
+        new_game.write(NewGame { seed: launch.seed, slot: None });
+    }
+    launch.skip_menu = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn inline_and_separate_values_match() {
+        let inline = parse(&["--seed=7", "--resolution=1280x720", "--load=slot_1"]).unwrap();
+        let separate = parse(&["--seed", "7", "--resolution", "1280x720", "--load", "slot_1"]).unwrap();
+        for cli in [inline, separate] {
+            assert_eq!(cli.seed, Some(7));
+            assert_eq!(cli.resolution, Some([1280, 720]));
+            assert_eq!(cli.load.as_deref(), Some("slot_1"));
+        }
+    }
+
+    #[test]
+    fn missing_value_is_an_error() {
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--windowed", "--load"]).is_err());
+    }
+
+    #[test]
+    fn bad_resolution_is_an_error() {
+        assert!(parse(&["--resolution", "1280"]).is_err());
+        assert!(parse(&["--resolution", "axb"]).is_err());
+        assert!(parse(&["--resolution", "0x720"]).is_err());
+        assert!(parse(&["--resolution", "1280x0"]).is_err());
+        assert_eq!(parse(&["--resolution", "800X600"]).unwrap().resolution, Some([800, 600]));
+    }
+
+    #[test]
+    fn bad_log_level_is_an_error() {
+        assert!(parse(&["--log-level", "loud"]).is_err());
+        assert_eq!(parse(&["--log-level", "warn"]).unwrap().log_level, Some(Level::WARN));
+    }
+
+    #[test]
+    fn unknown_flag_is_an_error() {
+        assert!(parse(&["--fast"]).is_err());
+    }
+
+    #[test]
+    fn ticks_require_headless() {
+        assert!(parse(&["--ticks", "100"]).is_err());
+        let cli = parse(&["--headless", "--ticks", "100"]).unwrap();
+        assert_eq!(cli.ticks, Some(100));
+        assert!(cli.launch_options().skip_menu);
+    }
+}
@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+use crate::resourses::physics_resources::*;
+use crate::systems::actions::{update_actions, Action, ActionState, Binding, InputMap, InputSource, HOTBAR_SLOTS};
+use crate::systems::menu_ui::{spawn_button, MenuAssets, NORMAL_BUTTON};
+use crate::systems::settings::{Language, Settings};
+
+pub struct ControlsPlugin;
+
+/// wychylenie gałki, od którego przypisujemy oś pada
+const CAPTURE_AXIS_THRESHOLD: f32 = 0.5;
+const CAPTURE_AXES: [GamepadAxis; 4] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+];
+
+/// Wiersze ekranu sterowania: akcja, kierunek osi, opis
+/// Skróty debugowania fizyki (--debug) celowo pomijamy
+const ROWS: [(Action, f32, &str); 13] = [
+    (Action::MoveY, 1.0, "Move up"),
+    (Action::MoveY, -1.0, "Move down"),
+    (Action::MoveX, -1.0, "Move left"),
+    (Action::MoveX, 1.0, "Move right"),
+    (Action::Sprint, 1.0, "Sprint"),
+    (Action::Attack, 1.0, "Attack"),
+    (Action::UseItem, 1.0, "Use item"),
+    (Action::NextSlot, 1.0, "Next slot"),
+    (Action::Pause, 1.0, "Pause"),
+    (Action::Interact, 1.0, "Interact"),
+    (Action::QuickSave, 1.0, "Quicksave"),
+    (Action::QuickLoad, 1.0, "Quickload"),
+    (Action::DebugOverlay, 1.0, "Debug overlay"),
+];
+
+/// Przycisk z przypisaniem akcji (kolumna klawiatury albo pada)
+#[derive(Component)]
+struct BindingButton {
+    action: Action,
+    scale: f32,
+}
+
+#[derive(Component)]
+struct BindingText {
+    action: Action,
+    scale: f32,
+    gamepad: bool,
+}
+
+#[derive(Component)]
+enum ControlsButton {
+    Reset,
+    Back,
+}
+
+/// Akcja czekająca na nowe wejście; mysz przypisujemy dopiero po puszczeniu,
+/// żeby klik nie trafił w przycisk pod kursorem
+#[derive(Resource, Default)]
+struct Rebinding {
+    target: Option<(Action, f32)>,
+    mouse_down: Option<MouseButton>,
+}
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Rebinding>()
+            .add_systems(OnEnter(MenuScreen::Controls), spawn_controls)
+            .add_systems(OnExit(MenuScreen::Controls), |mut rebinding: ResMut<Rebinding>| *rebinding = Rebinding::default())
+            .add_systems(PreUpdate, capture_binding.after(update_actions).run_if(in_state(MenuScreen::Controls)))
+            .add_systems(Update, (
+                binding_buttons,
+                controls_buttons,
+                refresh_bindings.run_if(resource_changed::<Settings>.or(resource_changed::<Rebinding>)),
+            ).chain().run_if(in_state(MenuScreen::Controls)));
+    }
+}
+
+/// Wszystkie wiersze razem z paskiem szybkiego wyboru
+fn rows() -> impl Iterator<Item = (Action, f32, String, &'static str)> {
+    ROWS.into_iter()
+        .map(|(action, scale, label)| (action, scale, String::new(), label))
+        .chain((0..HOTBAR_SLOTS).map(|slot| (Action::Hotbar(slot), 1.0, format!(" {}", slot + 1), "Slot")))
+}
+
+fn binding_text(controls: &InputMap, rebinding: &Rebinding, language: Language, action: Action, scale: f32, gamepad: bool) -> String {
+    if rebinding.target == Some((action, scale)) {
+        return "...".to_string();
+    }
+    let names: Vec<String> = controls.bindings_for(action, scale)
+        .filter(|binding| binding.input.is_gamepad() == gamepad)
+        .map(|binding| binding.input.name())
+        .collect();
+    if names.is_empty() {
+        language.text("None").to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn spawn_controls(mut commands: Commands, menu_assets: Res<MenuAssets>, settings: Res<Settings>, rebinding: Res<Rebinding>) {
+    let font = menu_assets.font.clone();
+    let language = settings.language;
+    let text_font = TextFont { font: font.clone(), font_size: 18.0, ..default() };
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        ImageNode::new(menu_assets.background.clone()),
+        MenuRoot,
+        DespawnOnExit(MenuScreen::Controls),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new(language.text("Controls")),
+            TextFont { font: font.clone(), font_size: 40.0, ..default() },
+            TextColor(Color::WHITE),
+            Node { margin: UiRect::bottom(Val::Px(10.0)), ..default() },
+        ));
+        parent.spawn((
+            Text::new(format!(
+                "{} | {}  -  {}",
+                language.text("Keyboard / mouse"),
+                language.text("Gamepad"),
+                language.text("Press a key or button (Esc cancels)"),
+            )),
+            TextFont { font: font.clone(), font_size: 18.0, ..default() },
+            TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            Node { margin: UiRect::bottom(Val::Px(10.0)), ..default() },
+        ));
+
+        // dwie kolumny wierszy - wszystkie nie mieszczą się w jednej
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Column,
+            flex_wrap: FlexWrap::Wrap,
+            height: Val::Px(560.0),
+            align_content: AlignContent::Center,
+            ..default()
+        })
+        .with_children(|list| {
+            for (action, scale, suffix, label) in rows() {
+                list.spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::horizontal(Val::Px(12.0)),
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        Text::new(format!("{}{}", language.text(label), suffix)),
+                        text_font.clone(),
+                        TextColor(Color::WHITE),
+                        Node { width: Val::Px(220.0), ..default() },
+                    ));
+                    for gamepad in [false, true] {
+                        row.spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(170.0),
+                                height: Val::Px(26.0),
+                                margin: UiRect::all(Val::Px(2.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(NORMAL_BUTTON),
+                            BindingButton { action, scale },
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                Text::new(binding_text(&settings.controls, &rebinding, language, action, scale, gamepad)),
+                                text_font.clone(),
+                                TextColor(Color::WHITE),
+                                BindingText { action, scale, gamepad },
+                            ));
+                        });
+                    }
+                });
+            }
+        });
+
+        parent.spawn(Node { margin: UiRect::top(Val::Px(10.0)), ..default() })
+            .with_children(|row| {
+                spawn_button(row, &font, language.text("Reset"), ControlsButton::Reset);
+                spawn_button(row, &font, language.text("Back"), ControlsButton::Back);
+            });
+    });
+}
+
+fn binding_buttons(
+    interaction_query: Query<(&Interaction, &BindingButton), Changed<Interaction>>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    if rebinding.target.is_some() {
+        return;
+    }
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            rebinding.target = Some((button.action, button.scale));
+        }
+    }
+}
+
+fn controls_buttons(
+    interaction_query: Query<(&Interaction, &ControlsButton), Changed<Interaction>>,
+    rebinding: Res<Rebinding>,
+    mut settings: ResMut<Settings>,
+    mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+    if rebinding.target.is_some() {
+        return;
+    }
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            ControlsButton::Reset => settings.controls = InputMap::default(),
+            ControlsButton::Back => next_screen.set(MenuScreen::Options),
+        }
+    }
+}
+
+/// Pierwsze wejście po kliknięciu przypisania zastępuje je; Escape anuluje
+fn capture_binding(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<Settings>,
+    mut actions: ResMut<ActionState>,
+) {
+    let Some((action, scale)) = rebinding.target else {
+        return;
+    };
+    // menu i gra nie reagują na wciśnięcia przeznaczone do przypisania
+    actions.clear();
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        *rebinding = Rebinding::default();
+        return;
+    }
+    if let Some(button) = mouse.get_just_pressed().next() {
+        rebinding.mouse_down = Some(*button);
+    }
+
+    let mut captured = keyboard.get_just_pressed().next().map(|key| (InputSource::Key(*key), false));
+    if let Some(button) = rebinding.mouse_down.filter(|button| mouse.just_released(*button)) {
+        captured = captured.or(Some((InputSource::Mouse(button), false)));
+    }
+    for gamepad in &gamepads {
+        if let Some(button) = gamepad.get_just_pressed().next() {
+            captured = captured.or(Some((InputSource::GamepadButton(*button), false)));
+        }
+        for axis in CAPTURE_AXES {
+            let value = gamepad.get(axis).unwrap_or(0.0);
+            if value.abs() >= CAPTURE_AXIS_THRESHOLD {
+                // wychylenie w przeciwną stronę odwraca oś
+                captured = captured.or(Some((InputSource::GamepadAxis(axis), value < 0.0)));
+            }
+        }
+    }
+
+    if let Some((input, invert)) = captured {
+        settings.controls.rebind(scale, Binding { action, input, scale, invert });
+        *rebinding = Rebinding::default();
+    }
+}
+
+fn refresh_bindings(
+    settings: Res<Settings>,
+    rebinding: Res<Rebinding>,
+    mut texts: Query<(&BindingText, &mut Text)>,
+) {
+    for (binding, mut text) in &mut texts {
+        let value = binding_text(&settings.controls, &rebinding, settings.language, binding.action, binding.scale, binding.gamepad);
+        if text.0 != value {
+            text.0 = value;
+        }
+    }
+}
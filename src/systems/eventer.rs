@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use crate::resourses::physics_resources::*;
-use bevy::window::{PrimaryWindow, Window};
+use crate::systems::physics::{PhysicsQuery, PhysicsQueryFilter};
+use crate::systems::actions::ActionState;
 
 pub struct EventerPlugin;
 
@@ -11,7 +12,7 @@ impl Plugin for EventerPlugin {
             .add_message::<FunctionalEvent>()
             //.init_resource::<Messages<ConsumeEvent>>()
             //.init_resource::<Messages<FunctionalEvent>>()
-            .add_systems(Update, (food_eventer, functional_eventer).run_if(|status: Res<GameStatus>, status2: Res<ResumeStatus>| status.0 && !status2.0));
+            .add_systems(Update, (food_eventer, functional_eventer).run_if(in_state(AppState::Playing)));
     }
 }
 
@@ -58,12 +59,13 @@ fn functional_eventer(
     asset_server: Res<AssetServer>,
     atlas_handles: Res<AtlasHandles>,
     mut query_m: Query<(&mut MonsterAI, &Transform), (With<Monster>, Without<Player>, Without<Pending>)>,
-    mut query_p: Query<&mut PlayerData, With<Player>>,
-    mouse: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    mut query_p: Query<(Entity, &mut PlayerData), With<Player>>,
+    actions: Res<ActionState>,
+    physics_query: PhysicsQuery,
+    mut knockback: MessageWriter<ApplyKnockback>,
 ) {
     for ev in events.read() {
-        let Ok(mut pdata) = query_p.single_mut() else {
+        let Ok((player, mut pdata)) = query_p.single_mut() else {
             return;
         };
         if let Some(item) = config.items.get(&ev.item_id) {
@@ -78,33 +80,37 @@ fn functional_eventer(
                     *indices = animation_indices;
                     timer.reset();
                     let tile_size = TILE_SIZE;
-                    let action_distance = 1.675 * tile_size;
-                    for (mut ai, rb_transform) in &mut query_m {
-                        let monster_pos = rb_transform.translation.xy();
-                        let player_pos = transform.translation().xy();
-                        let distance = monster_pos.distance(player_pos);
-                        if distance < action_distance {
-                            let window = match windows.single() {
-                                Ok(w) => w,
-                                Err(_) => return, // brak okna głównego
-                            };
+                    let player_pos = transform.translation().xy();
+                    // bez kursora cios idzie w stronę, w którą patrzy gracz
+                    let facing = Vec2::new(transform.affine().matrix3.x_axis.x.signum(), 0.0);
+                    let cursor_dir = actions.aim().unwrap_or(facing);
+
+                    // obszar ciosu: okrąg wokół gracza + dalszy okrąg w stronę kursora
+                    let near = BodyShape::Ball { radius: tile_size };
+                    let swing = BodyShape::Ball { radius: 0.6 * tile_size };
+                    let monsters = PhysicsQueryFilter::layers(&[CollisionLayer::Monster, CollisionLayer::FlyingMonster]);
+                    let mut hits = physics_query.intersect_shape(&near, player_pos, monsters);
+                    hits.extend(physics_query.intersect_shape(&swing, player_pos + cursor_dir * 1.1 * tile_size, monsters));
+                    hits.sort();
+                    hits.dedup();
 
-                            if let Some(cursor_pos) = window.cursor_position() {
-                                let to_monster = (monster_pos - player_pos).normalize_or_zero();
-                                let screen_center = Vec2::new(window.width() / 2.0, window.height() / 2.0);
-                                let fixed_cursor_pos = Vec2::new(cursor_pos.x, window.height() - cursor_pos.y);
-                                let cursor_dir = (fixed_cursor_pos - screen_center).normalize_or_zero();
-                                let dot = cursor_dir.dot(to_monster).clamp(-1.0, 1.0);
-                                let angle = dot.acos().to_degrees();
-                                if angle < 75.0 {
-                                    ai.health -= item.value[0];
-                                    ai.stun_cooldown.reset();
-                                } else if distance < 1.25 * tile_size {
-                                    ai.health -= item.value[0];
-                                    ai.stun_cooldown.reset();
-                                }
-                            }
+                    let walls = PhysicsQueryFilter::layers(&[CollisionLayer::Wall]).excluding(player);
+                    for monster in hits {
+                        let Ok((mut ai, rb_transform)) = query_m.get_mut(monster) else {
+                            continue;
+                        };
+                        let monster_pos = rb_transform.translation.xy();
+                        // nie bijemy przez ściany
+                        if !physics_query.line_of_sight(player_pos, monster_pos, walls) {
+                            continue;
                         }
+                        ai.health -= item.value[0];
+                        knockback.write(ApplyKnockback {
+                            target: monster,
+                            direction: monster_pos - player_pos,
+                            impulse: item.knockback,
+                            stun: item.stun,
+                        });
                     }
                 }
             }
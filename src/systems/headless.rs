@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use bevy::app::PluginGroupBuilder;
+use bevy::camera::visibility::VisibilityPlugin;
+use bevy::image::TextureAtlasPlugin;
+use bevy::input::InputPlugin;
+use bevy::mesh::MeshPlugin;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use crate::resourses::physics_resources::*;
+
+/// Silnik bez okna i GPU: MinimalPlugins + stany, transformacje, widoczność, wejście i zasoby
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add_group(MinimalPlugins)
+            .add(StatesPlugin)
+            .add(TransformPlugin)
+            // Visibility dla Mesh2d - ta sama hierarchia encji co z oknem
+            .add(VisibilityPlugin)
+            .add(InputPlugin)
+            .add(AssetPlugin::default())
+            .add(MeshPlugin)
+            .add(ImagePlugin::default_nearest())
+            .add(TextureAtlasPlugin)
+            .add(HeadlessPlugin::default())
+    }
+}
+
+/// Symulacja bez menu: od razu gra, stały krok czasu i opcjonalny limit ticków
+#[derive(Default)]
+pub struct HeadlessPlugin {
+    /// po tylu tickach FixedUpdate w grze aplikacja się kończy
+    pub ticks: Option<u32>,
+}
+
+/// Ticki FixedUpdate przebiegnięte w stanie Playing
+#[derive(Resource, Default)]
+pub struct SimulationTicks {
+    pub done: u32,
+    pub limit: Option<u32>,
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(frame_step(&PhysicsTimestep::default()))
+            .insert_resource(SimulationTicks { done: 0, limit: self.ticks })
+            .add_systems(Update, match_frame_to_timestep.run_if(resource_exists_and_changed::<PhysicsTimestep>))
+            .add_systems(Update, skip_loading.run_if(in_state(AppState::Loading)))
+            .add_systems(OnEnter(AppState::GameOver), exit_on_game_over)
+            .add_systems(FixedUpdate, count_ticks.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Każda klatka = dokładnie jeden krok fizyki, niezależnie od zegara
+fn frame_step(timestep: &PhysicsTimestep) -> TimeUpdateStrategy {
+    TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / timestep.hz))
+}
+
+/// Po zmianie PhysicsTimestep klatka dalej odpowiada jednemu tickowi (jak Time<Fixed> w apply_physics_timestep)
+pub fn match_frame_to_timestep(timestep: Res<PhysicsTimestep>, mut strategy: ResMut<TimeUpdateStrategy>) {
+    *strategy = frame_step(&timestep);
+}
+
+/// Bez menu nie ma czego ładować - CliPlugin od razu uruchamia grę
+fn skip_loading(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::MainMenu);
+}
+
+/// Bez menu końca gry nie ma dokąd wrócić
+fn exit_on_game_over(ticks: Res<SimulationTicks>, score: Res<Score>, mut exit: MessageWriter<AppExit>) {
+    info!("Gracz zginął po {} tickach, punkty {}", ticks.done, score.0);
+    exit.write(AppExit::Success);
+}
+
+fn count_ticks(
+    mut ticks: ResMut<SimulationTicks>,
+    player: Query<(&Transform, &PlayerData), With<Player>>,
+    score: Res<Score>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    ticks.done += 1;
+    if ticks.limit.is_some_and(|limit| ticks.done >= limit) {
+        match player.single() {
+            Ok((transform, data)) => info!(
+                "Koniec symulacji po {} tickach: gracz ({:.1}, {:.1}), zdrowie {:.1}, punkty {}",
+                ticks.done, transform.translation.x, transform.translation.y, data.health, score.0,
+            ),
+            Err(_) => info!("Koniec symulacji po {} tickach: brak gracza, punkty {}", ticks.done, score.0),
+        }
+        exit.write(AppExit::Success);
+    }
+}
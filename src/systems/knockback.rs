@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use crate::resourses::physics_resources::*;
+
+pub struct KnockbackPlugin;
+
+/// wygaszanie prędkości odrzutu (1/s)
+const KNOCKBACK_DAMPING: f32 = 8.0;
+/// poniżej tej prędkości odrzut jest usuwany
+const KNOCKBACK_MIN_SPEED: f32 = 5.0;
+
+impl Plugin for KnockbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ApplyKnockback>()
+            .add_systems(FixedUpdate, damp_knockback.run_if(in_state(AppState::Playing)))
+            .add_systems(Update, (apply_knockback, tick_stun).chain()
+                .run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Zamienia impuls na prędkość odrzutu (v = J / m) i nakłada ogłuszenie, oba osłabione odpornością
+fn apply_knockback(
+    mut commands: Commands,
+    mut events: MessageReader<ApplyKnockback>,
+    mut targets: Query<(Option<&KnockbackProps>, Option<&mut Knockback>, Option<&mut Stunned>)>,
+) {
+    for ev in events.read() {
+        let Ok((props, knockback, stunned)) = targets.get_mut(ev.target) else {
+            continue;
+        };
+        let props = props.copied().unwrap_or_default();
+        let factor = (1.0 - props.resistance).clamp(0.0, 1.0);
+        let velocity = ev.direction.normalize_or_zero() * ev.impulse / props.mass.max(0.01) * factor;
+        match knockback {
+            Some(mut knockback) => knockback.0 += velocity,
+            None => {
+                commands.entity(ev.target).insert(Knockback(velocity));
+            }
+        }
+
+        let stun = ev.stun * factor;
+        if stun <= 0.0 {
+            continue;
+        }
+        match stunned {
+            // dłuższe ogłuszenie wygrywa
+            Some(mut stunned) if stunned.0.remaining_secs() < stun => {
+                stunned.0 = Timer::from_seconds(stun, TimerMode::Once);
+            }
+            Some(_) => {}
+            None => {
+                commands.entity(ev.target).insert(Stunned(Timer::from_seconds(stun, TimerMode::Once)));
+            }
+        }
+    }
+}
+
+fn tick_stun(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Stunned)>,
+) {
+    for (entity, mut stunned) in &mut query {
+        if stunned.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
+
+fn damp_knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Knockback)>,
+) {
+    let decay = (-KNOCKBACK_DAMPING * time.delta_secs()).exp();
+    for (entity, mut knockback) in &mut query {
+        knockback.0 *= decay;
+        if knockback.0.length() < KNOCKBACK_MIN_SPEED {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
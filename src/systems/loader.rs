@@ -1,16 +1,43 @@
 use bevy::prelude::*;
 use bevy::mesh::{Mesh, VertexAttributeValues, Indices, PrimitiveTopology};
 use crate::resourses::physics_resources::*;
+use crate::systems::physics::remove_orphan_body;
 
 use rapier2d::prelude::*;
 use rapier2d::na::Point2;
+use serde::de::DeserializeOwned;
+use std::fs;
 
 pub struct ObjectsLoaderPlugin;
 
+const CONTACT_FORCE_THRESHOLD: f32 = 1000.0;
+
 impl Plugin for ObjectsLoaderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, init)
-            .add_systems(Update, inspect);
+        // stan gry zaczyna się od Loading - konfiguracja wczytuje się w Startup
+        app.init_state::<AppState>()
+            .init_resource::<AtlasHandles>()
+            .add_systems(Startup, (
+                init,
+                load_config::<ItemConfig>("assets/config/items.json"),
+                load_config::<BiomeConfig>("assets/config/biomes.json"),
+                load_config::<CollisionLayerConfig>("assets/config/collision_layers.json"),
+                load_config::<StructureConfig>("assets/config/structures.json"),
+            ))
+            .add_systems(Update, inspect.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// System wczytujący plik konfiguracji JSON jako zasób
+fn load_config<T: DeserializeOwned + Resource>(path: &'static str) -> impl Fn(Commands) {
+    move |mut commands: Commands| {
+        let data = fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("Nie można wczytać pliku konfiguracyjnego {}: {}", path, err));
+
+        let config: T = serde_json::from_str(&data)
+            .unwrap_or_else(|err| panic!("Błąd parsowania pliku JSON {}: {}", path, err));
+
+        commands.insert_resource(config);
     }
 }
 
@@ -25,38 +52,110 @@ fn init(
     atlas_handles.0.insert("attack".to_string(), handle_1);
 }
 
-fn inspect(
+pub(crate) fn inspect(
     mut commands: Commands,
     meshes: Res<Assets<Mesh>>,
-    query: Query<(Entity, &Mesh2d, &Transform, Option<&Player>, Option<&Wall>, Option<&Floor>), With<Pending>>,
+    query: Query<(
+        Entity,
+        &Transform,
+        &PhysicsBody,
+        &BodyShape,
+        Option<&ColliderProps>,
+        Option<&CollisionLayer>,
+        Option<&ProjectileOwner>,
+        Option<&Mesh2d>,
+        Has<NoInterpolation>,
+    ), With<Pending>>,
+    layer_config: Res<CollisionLayerConfig>,
     mut rigid_bodies: ResMut<ResRigidBodySet>,
     mut colliders: ResMut<ResColliderSet>,
+    mut entity_map: ResMut<PhysicsEntityMap>,
+    mut owners: ResMut<ProjectileOwners>,
 ) {
-    for (entity, mesh_handle, transform, player, wall, floor) in &query {
-        if let Some(mesh) = meshes.get(&mesh_handle.0) {
-            if let Some((vertices, indices)) = handle_mesh(mesh, transform) {
-                let rigid_body = if player.is_some() {
-                    RigidBodyBuilder::dynamic().soft_ccd_prediction(0.0).lock_rotations()
-                } else if wall.is_some() || floor.is_some() {
-                    RigidBodyBuilder::fixed()
-                } else {
-                    RigidBodyBuilder::dynamic().soft_ccd_prediction(0.0).lock_rotations()
-                }.translation(vector![transform.translation.x, transform.translation.y])
-                    .build();
-                let rb_handle = rigid_bodies.0.insert(rigid_body);
-                let collider = ColliderBuilder::trimesh_with_flags(vertices, indices, TriMeshFlags::MERGE_DUPLICATE_VERTICES).expect("REASON")
-                    .restitution(0.0)
-                    .friction(0.5)
-                    .restitution_combine_rule(CoefficientCombineRule::Average)
-                    .friction_combine_rule(CoefficientCombineRule::Average)
-                    .build();
-                let col_handle = colliders.0.insert_with_parent(collider, rb_handle, &mut rigid_bodies.0);
-                commands.entity(entity).insert((
-                    RigidBodyHandleComponent(rb_handle),
-                ));
-                commands.entity(entity).remove::<Pending>();
+    for (entity, transform, body, shape, props, layer, owner, mesh_handle, no_interpolation) in &query {
+        let scale = transform.scale;
+        let collider_builder = match *shape {
+            BodyShape::Cuboid { half_extents } => {
+                ColliderBuilder::cuboid(half_extents.x * scale.x.abs(), half_extents.y * scale.y.abs())
+            }
+            BodyShape::Ball { radius } => ColliderBuilder::ball(radius * scale.x.abs()),
+            BodyShape::Capsule { half_height, radius } => {
+                ColliderBuilder::capsule_y(half_height * scale.y.abs(), radius * scale.x.abs())
+            }
+            BodyShape::Trimesh => {
+                // mesh może się jeszcze nie wczytać - zostajemy w Pending
+                let Some(mesh) = mesh_handle.and_then(|handle| meshes.get(&handle.0)) else {
+                    continue;
+                };
+                let Some((vertices, indices)) = handle_mesh(mesh, transform) else {
+                    continue;
+                };
+                ColliderBuilder::trimesh_with_flags(vertices, indices, TriMeshFlags::MERGE_DUPLICATE_VERTICES).expect("REASON")
+            }
+        };
+
+        let rigid_body = match body {
+            PhysicsBody::Dynamic => RigidBodyBuilder::dynamic().soft_ccd_prediction(0.0).lock_rotations(),
+            PhysicsBody::Fixed => RigidBodyBuilder::fixed(),
+            PhysicsBody::Kinematic => RigidBodyBuilder::kinematic_position_based().lock_rotations(),
+        }.translation(vector![transform.translation.x, transform.translation.y])
+            .build();
+        let rb_handle = rigid_bodies.0.insert(rigid_body);
+
+        // tylko ruchome ciała zgłaszają siły kontaktu (ściany/podłoga nie muszą)
+        let active_events = if *body == PhysicsBody::Fixed {
+            ActiveEvents::COLLISION_EVENTS
+        } else {
+            ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS
+        };
+        // domyślnie rapier nie zgłasza kolizji kinematyczne-stałe/kinematyczne (np. postać w sensorze)
+        let collision_types = if *body == PhysicsBody::Kinematic {
+            ActiveCollisionTypes::all()
+        } else {
+            ActiveCollisionTypes::default()
+        };
+        let props = props.copied().unwrap_or_default();
+        let groups = layer
+            .map(|layer| layer_config.groups(*layer))
+            .unwrap_or(InteractionGroups::all());
+        // pocisk nie trafia swojego właściciela
+        let hooks = if let Some(owner) = owner {
+            owners.0.insert(entity, owner.0);
+            ActiveHooks::FILTER_CONTACT_PAIRS | ActiveHooks::FILTER_INTERSECTION_PAIR
+        } else {
+            ActiveHooks::empty()
+        };
+        let collider = collider_builder
+            .collision_groups(groups)
+            .active_hooks(hooks)
+            .active_events(active_events)
+            .active_collision_types(collision_types)
+            .contact_force_event_threshold(CONTACT_FORCE_THRESHOLD)
+            .sensor(props.sensor)
+            .density(props.density)
+            .restitution(props.restitution)
+            .friction(props.friction)
+            .restitution_combine_rule(CoefficientCombineRule::Average)
+            .friction_combine_rule(CoefficientCombineRule::Average)
+            .build();
+        let col_handle = colliders.0.insert_with_parent(collider, rb_handle, &mut rigid_bodies.0);
+        entity_map.insert_body(entity, rb_handle);
+        entity_map.insert_collider(entity, col_handle);
+        // encja mogła zostać usunięta w tej samej klatce (np. przy wczytaniu gry) - wtedy ciało też znika
+        commands.queue(move |world: &mut World| {
+            let handles = (RigidBodyHandleComponent(rb_handle), ColliderComponent(col_handle));
+            if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.insert(handles);
+            } else if let Err(err) = world.run_system_cached_with(remove_orphan_body, (entity, rb_handle)) {
+                error!("Nie udało się usunąć ciała encji {}: {}", entity, err);
             }
+        });
+        // nieruchome ciała nie potrzebują interpolacji
+        if *body != PhysicsBody::Fixed && !no_interpolation {
+            let rotation = transform.rotation.to_euler(EulerRot::XYZ).2;
+            commands.entity(entity).try_insert(PhysicsPose::new(transform.translation.xy(), rotation));
         }
+        commands.entity(entity).try_remove::<Pending>();
     }
 }
 
@@ -1,7 +1,12 @@
 
 use bevy::prelude::*;
 use bevy::app::AppExit;
+use bevy::asset::{LoadState, UntypedAssetId};
 use crate::resourses::physics_resources::*;
+use crate::systems::actions::{Action, ActionState};
+use crate::systems::cli::LaunchOptions;
+use crate::systems::save::CurrentSlot;
+use crate::systems::settings::{Language, Settings};
 
 use bevy_2d_screen_space_lightmaps::lightmap_plugin::lightmap_plugin::*;
 use bevy::camera::visibility::RenderLayers;
@@ -12,35 +17,125 @@ const OCCLUDER_LIGHT_LAYER_Z: f32 = 1.0;
 const SPRITE_FLOOR_LAYER_Z: f32 = 0.0;
 const SPRITE_OBJECT_LAYER_Z: f32 = 1.0;
 
-const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+pub const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
 const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
 const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
 pub struct MenuPlugin;
 
+/// Zasoby menu wczytywane w stanie Loading
+#[derive(Resource)]
+pub struct MenuAssets {
+    pub font: Handle<Font>,
+    pub background: Handle<Image>,
+}
+
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(GameStatus(false)).insert_resource(ResumeStatus(false))
-            .add_systems(Startup, init)
-           .add_systems(Update, button_system);
+        app.add_sub_state::<MenuScreen>()
+            .add_systems(Startup, load_menu_assets)
+            .add_systems(Update, finish_loading.run_if(in_state(AppState::Loading)))
+            .add_systems(OnEnter(AppState::MainMenu), |mut commands: Commands| spawn_menu_camera(&mut commands, AppState::MainMenu))
+            .add_systems(OnEnter(MenuScreen::Main), spawn_main_menu)
+            .add_systems(OnEnter(AppState::GameOver), spawn_game_over_menu)
+            .add_systems(Update, (
+                button_system.run_if(in_state(AppState::MainMenu).or(in_state(AppState::Paused)).or(in_state(AppState::GameOver))),
+                toggle_pause.run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))),
+            ));
+    }
+}
+
+fn load_menu_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MenuAssets {
+        font: asset_server.load("fonts/Cantarell-Bold.ttf"),
+        background: asset_server.load("textures/menu.png"),
+    });
+}
+
+/// Przejście do menu, gdy zasoby są gotowe (albo nie dały się wczytać)
+fn finish_loading(
+    asset_server: Res<AssetServer>,
+    menu_assets: Res<MenuAssets>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let ready = |id: UntypedAssetId| matches!(
+        asset_server.get_load_state(id),
+        Some(LoadState::Loaded) | Some(LoadState::Failed(_))
+    );
+    if ready(menu_assets.font.id().untyped()) && ready(menu_assets.background.id().untyped()) {
+        next_state.set(AppState::MainMenu);
     }
 }
 
-fn init(mut commands: Commands, asset_server: Res<AssetServer>) {
-    setup_ui(&mut commands, &asset_server);
+pub fn spawn_menu_camera(commands: &mut Commands, state: AppState) {
     commands.spawn((
         Camera2d,
         //SpriteCamera, AnyNormalCamera,
-        MenuCamera
+        MenuCamera,
+        DespawnOnExit(state),
     ));
 }
 
-pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+/// Pierwszy ekran menu głównego albo pauzy. Kamera menu głównego żyje przez wszystkie
+/// jego ekrany, w pauzie kamera gracza nadal istnieje
+fn spawn_main_menu(
+    mut commands: Commands,
+    menu_assets: Res<MenuAssets>,
+    settings: Res<Settings>,
+    state: Res<State<AppState>>,
+) {
+    setup_ui(&mut commands, &menu_assets, *state.get(), settings.language);
+}
+
+fn spawn_game_over_menu(mut commands: Commands, menu_assets: Res<MenuAssets>, settings: Res<Settings>) {
+    setup_ui(&mut commands, &menu_assets, AppState::GameOver, settings.language);
+    spawn_menu_camera(&mut commands, AppState::GameOver);
+}
+
+/// Akcja Pause (Escape): pauza, powrót do gry albo z podekranu pauzy (np. opcji) do menu pauzy
+fn toggle_pause(
+    actions: Res<ActionState>,
+    state: Res<State<AppState>>,
+    screen: Option<Res<State<MenuScreen>>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+    if !actions.just_pressed(Action::Pause) {
+        return;
+    }
+    match state.get() {
+        AppState::Playing => next_state.set(AppState::Paused),
+        AppState::Paused if screen.is_some_and(|screen| *screen.get() != MenuScreen::Main) => {
+            next_screen.set(MenuScreen::Main);
+        }
+        AppState::Paused => next_state.set(AppState::Playing),
+        _ => {}
+    }
+}
 
-    // font used by buttons
-    let font = asset_server.load("fonts/Cantarell-Bold.ttf");
+/// Menu dla danego stanu, usuwane przy wyjściu z niego
+pub fn setup_ui(commands: &mut Commands, menu_assets: &MenuAssets, state: AppState, language: Language) {
+    let font = menu_assets.font.clone();
+    let buttons: &[(MenuButtonAction, &'static str)] = match state {
+        AppState::Paused => &[
+            (MenuButtonAction::Resume, "Resume"),
+            (MenuButtonAction::SaveGame, "Save Game"),
+            (MenuButtonAction::Options, "Options"),
+            (MenuButtonAction::Exit, "Exit"),
+        ],
+        AppState::GameOver => &[
+            (MenuButtonAction::NewGame, "New Game"),
+            (MenuButtonAction::Exit, "Exit"),
+        ],
+        _ => &[
+            (MenuButtonAction::NewGame, "New Game"),
+            (MenuButtonAction::LoadGame, "Load Game"),
+            (MenuButtonAction::Options, "Options"),
+            (MenuButtonAction::Exit, "Exit"),
+        ],
+    };
 
     // root node: full-screen, centered column
-    commands.spawn((
+    let mut root = commands.spawn((
         Node {
             width: Val::Percent(100.0),
             height: Val::Percent(100.0),
@@ -50,121 +145,74 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
             padding: UiRect::top(Val::Percent(13.5)),
             ..default()
         },
-        ImageNode::new(asset_server.load("textures/menu.png")),
+        ImageNode::new(menu_assets.background.clone()),
         MenuRoot,
-    ))
-    .with_children(|parent| {
+    ));
+    root.with_children(|parent| {
         // optional spacer / logo area
         parent.spawn((
             Node { margin: UiRect::bottom(Val::Px(20.0)), ..default() },
         ));
 
-        // buttons (stacked vertically)
-        parent.spawn((
-            Node { margin: UiRect::bottom(Val::Px(20.0)), ..default() },
-        ));
-        parent.spawn((
-            Button,
-            Node {
-                width: Val::Px(220.0),
-                height: Val::Px(60.0),
-                margin: UiRect::all(Val::Px(8.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            BackgroundColor(NORMAL_BUTTON),
-            BorderColor::all(Color::BLACK),
-            MenuButton(MenuButtonAction::NewGame),
-        ))
-        .with_children(|parent| {
-            parent.spawn((
-                Text::new("New Game"),
-                TextFont { font: font.clone(), font_size: 28.0, ..default() },
-                TextColor(Color::WHITE),
-            ));
-        });
-        parent.spawn((
-            Button,
-            Node {
-                width: Val::Px(220.0),
-                height: Val::Px(60.0),
-                margin: UiRect::all(Val::Px(8.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            BackgroundColor(NORMAL_BUTTON),
-            BorderColor::all(Color::BLACK),
-            MenuButton(MenuButtonAction::LoadGame),
-        ))
-        .with_children(|parent| {
+        if state == AppState::GameOver {
             parent.spawn((
-                Text::new("Load Game"),
-                TextFont { font: font.clone(), font_size: 28.0, ..default() },
+                Text::new(language.text("Game Over")),
+                TextFont { font: font.clone(), font_size: 48.0, ..default() },
                 TextColor(Color::WHITE),
+                Node { margin: UiRect::bottom(Val::Px(20.0)), ..default() },
             ));
-        });
-        parent.spawn((
-            Button,
-            Node {
-                width: Val::Px(220.0),
-                height: Val::Px(60.0),
-                margin: UiRect::all(Val::Px(8.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            BackgroundColor(NORMAL_BUTTON),
-            BorderColor::all(Color::BLACK),
-            MenuButton(MenuButtonAction::Options),
-        ))
-        .with_children(|parent| {
+        } else {
             parent.spawn((
-                Text::new("Options"),
-                TextFont { font: font.clone(), font_size: 28.0, ..default() },
-                TextColor(Color::WHITE),
+                Node { margin: UiRect::bottom(Val::Px(20.0)), ..default() },
             ));
-        });
+        }
+
+        // buttons (stacked vertically)
+        for (action, label) in buttons {
+            spawn_button(parent, &font, language.text(label), MenuButton(*action));
+        }
+    });
+    // menu główne i pauza mają kilka ekranów - ten znika także przy przejściu np. do opcji
+    if matches!(state, AppState::MainMenu | AppState::Paused) {
+        root.insert(DespawnOnExit(MenuScreen::Main));
+    } else {
+        root.insert(DespawnOnExit(state));
+    }
+}
+
+pub fn spawn_button(parent: &mut ChildSpawnerCommands, font: &Handle<Font>, label: &str, action: impl Bundle) {
+    parent.spawn((
+        Button,
+        Node {
+            width: Val::Px(220.0),
+            height: Val::Px(60.0),
+            margin: UiRect::all(Val::Px(8.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(NORMAL_BUTTON),
+        BorderColor::all(Color::BLACK),
+        action,
+    ))
+    .with_children(|parent| {
         parent.spawn((
-            Button,
-            Node {
-                width: Val::Px(220.0),
-                height: Val::Px(60.0),
-                margin: UiRect::all(Val::Px(8.0)),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            BackgroundColor(NORMAL_BUTTON),
-            BorderColor::all(Color::BLACK),
-            MenuButton(MenuButtonAction::Exit),
-        ))
-        .with_children(|parent| {
-            parent.spawn((
-                Text::new("Exit"),
-                TextFont { font: font.clone(), font_size: 28.0, ..default() },
-                TextColor(Color::WHITE),
-            ));
-        });
+            Text::new(label),
+            TextFont { font: font.clone(), font_size: 28.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
     });
 }
 
 fn button_system(
-    mut commands: Commands,
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor, Option<&MenuButton>), (Changed<Interaction>, With<Button>)>,
-    menu_root_query: Query<Entity, With<MenuRoot>>,
     mut exit: MessageWriter<AppExit>,
-    mut game_status: ResMut<GameStatus>,
-    mut resume_status: ResMut<ResumeStatus>,
-    camera_query: Query<Entity, With<MenuCamera>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    images: Res<Assets<Image>>,
-    config: Res<ItemConfig>,
-    atlas_handles: Res<AtlasHandles>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut new_game: MessageWriter<NewGame>,
+    mut save_game: MessageWriter<SaveGame>,
+    mut next_screen: ResMut<NextState<MenuScreen>>,
+    current_slot: Res<CurrentSlot>,
+    launch: Res<LaunchOptions>,
 ) {
     for (interaction, mut bg_color, menu_button) in &mut interaction_query {
         match *interaction {
@@ -173,38 +221,24 @@ fn button_system(
                 if let Some(btn) = menu_button {
                     match btn.0 {
                         MenuButtonAction::NewGame => {
-                            if !game_status.0 {
-                                for root in menu_root_query.iter() {
-                                    commands.entity(root).despawn();
-                                    for entity in camera_query {
-                                        commands.entity(entity).despawn();
-                                    }
-                                    crate::systems::player_game_ui::spawn_health_bar(&mut commands, &asset_server);
-                                    crate::systems::player_game_ui::spawn_inventory_bar(&mut commands, &asset_server);
-                                    crate::systems::player::init(&mut commands, &mut meshes, &mut materials, &asset_server, &mut texture_atlas_layouts, &images, &config, &atlas_handles);
-                                    game_status.0 = true;
-                                }
-                            }
+                            // świat i gracza stawia save::start_game, menu i HUD OnEnter/DespawnOnExit
+                            new_game.write(NewGame { seed: launch.seed, slot: None });
+                        }
+                        MenuButtonAction::LoadGame => {
+                            next_screen.set(MenuScreen::SaveSlots);
+                        }
+                        MenuButtonAction::SaveGame => {
+                            save_game.write(SaveGame { slot: current_slot.0.clone() });
+                        }
+                        MenuButtonAction::Resume => {
+                            next_state.set(AppState::Playing);
                         }
                         MenuButtonAction::Exit => {
                             // close the app
                             exit.write(AppExit::Success);
                         }
-                        MenuButtonAction::LoadGame => {
-                            if game_status.0 {
-                                resume_status.0 = false;
-                                for root in menu_root_query.iter() {
-                                    commands.entity(root).despawn();
-                                    for entity in camera_query {
-                                        commands.entity(entity).despawn();
-                                    }
-                                }
-                                crate::systems::player_game_ui::spawn_health_bar(&mut commands, &asset_server);
-                                crate::systems::player_game_ui::spawn_inventory_bar(&mut commands, &asset_server);
-                            }
-                        }
                         MenuButtonAction::Options => {
-                            // no-op (placeholder)
+                            next_screen.set(MenuScreen::Options);
                         }
                     }
                 }
@@ -5,4 +5,19 @@ pub mod terrain;
 pub mod loader;
 pub mod player_game_ui;
 pub mod menu_ui;
-pub mod eventer;
\ No newline at end of file
+pub mod eventer;
+pub mod tile_sim;
+pub mod seasons;
+pub mod physics_debug;
+pub mod knockback;
+pub mod structures;
+pub mod save;
+pub mod save_slots_ui;
+pub mod autosave;
+pub mod settings;
+pub mod options_ui;
+pub mod actions;
+pub mod controls_ui;
+pub mod cli;
+pub mod render;
+pub mod headless;
\ No newline at end of file
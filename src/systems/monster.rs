@@ -1,7 +1,9 @@
 use bevy::prelude::*;
-use rapier2d::prelude::*;
 
 use crate::resourses::physics_resources::*;
+use crate::systems::terrain::{avoid_hazards, TerrainMap};
+use crate::systems::physics::{PhysicsQuery, PhysicsQueryFilter};
+use crate::systems::settings::Settings;
 use bevy::camera::{RenderTarget, ImageRenderTarget};
 
 pub struct MonsterPlugin;
@@ -11,9 +13,14 @@ struct MonsterSpawnTimer(Timer);
 
 //use bevy_2d_screen_space_lightmaps::lightmap_plugin::lightmap_plugin::*;
 use bevy::camera::visibility::RenderLayers;
-use bevy_firefly::prelude::*;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages, TextureDescriptor};
 
+/// odrzut i ogłuszenie gracza po ciosie potwora
+const MONSTER_HIT_KNOCKBACK: f32 = 350.0;
+const MONSTER_HIT_STUN: f32 = 0.2;
+/// obrażenia ciosu potwora na normalnym poziomie trudności
+const MONSTER_HIT_DAMAGE: f32 = 20.0;
+
 #[derive(Resource)]
 struct MonsterConfig {
     min_spawn_distance: f32,   // w tileach
@@ -27,6 +34,7 @@ struct MonsterConfig {
 impl Plugin for MonsterPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(MonsterSpawnTimer(Timer::from_seconds(1.0, TimerMode::Repeating)))
+           .init_resource::<Score>()
            .insert_resource(MonsterConfig {
                min_spawn_distance: 20.0,   // spawn 20 kratek
                max_despawn_distance: 30.0, // despawn 30 kratek
@@ -35,8 +43,17 @@ impl Plugin for MonsterPlugin {
                world_size_y: (WORLD_SIZE/3) as usize,
                tile_size: 64.0,
            })
-           .add_systems(Update, spawn_monsters_system.run_if(|status: Res<GameStatus>, status2: Res<ResumeStatus>| status.0 && !status2.0))
-           .add_systems(Update, (monster_ai, animate_monster_sprite).run_if(|status: Res<GameStatus>, status2: Res<ResumeStatus>| status.0 && !status2.0));
+           .add_systems(Update, spawn_monsters_system.run_if(in_state(AppState::Playing)))
+           .add_systems(Update, (monster_sight, monster_ai, animate_monster_sprite).chain().run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Część potworów potrzebna tylko do wyświetlania
+pub struct MonsterRenderPlugin;
+
+impl Plugin for MonsterRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, attach_ai_camera);
     }
 }
 
@@ -51,16 +68,17 @@ fn spawn_monsters_system(
     existing_monsters: Query<Entity, With<Monster>>,
     config: Res<MonsterConfig>,
     atlas_handles: Res<AtlasHandles>,
-    mut images: ResMut<Assets<Image>>,
-    menu_root_query: Query<Entity, (With<HealthBar>, Without<DebugAI>)>,
+    clock: Res<GameClock>,
 ) {
     timer.0.tick(time.delta());
     if !timer.0.just_finished() {
         return;
     }
 
+    // limit potworów zależy od pory roku
+    let max_monsters = config.max_monsters + clock.season().extra_monsters();
     let current_count = existing_monsters.iter().count();
-    if current_count >= config.max_monsters {
+    if current_count >= max_monsters {
         return;
     }
 
@@ -70,19 +88,14 @@ fn spawn_monsters_system(
         return;
     };
 
-    let texture = asset_server.load("textures/monster_combined.png");
-    let layout = TextureAtlasLayout::from_grid(bevy::prelude::UVec2::splat(64), 2, 5, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
     let spawn_distance = config.min_spawn_distance * config.tile_size;
-    let to_spawn = config.max_monsters - current_count;
+    let to_spawn = max_monsters - current_count;
 
     // granice mapy w pikselach
     let map_min_x = -(config.world_size_x as f32 * config.tile_size) / 2.0;
     let map_max_x = (config.world_size_x as f32 * config.tile_size) / 2.0;
     let map_min_y = -(config.world_size_y as f32 * config.tile_size) / 2.0;
     let map_max_y = (config.world_size_y as f32 * config.tile_size) / 2.0;
-    let mut spawned = false;
     for _ in 0..to_spawn {
         let mut pos;
         let mut attempts = 0;
@@ -100,83 +113,111 @@ fn spawn_monsters_system(
             attempts += 1;
             if attempts > 5 { break; } // unikamy nieskończonej pętli
         }
-        let monster_animation_indices = atlas_handles.0.get("walk").unwrap().clone();
-        let image_handle = create_ai_texture(&mut images, 1024, 1024);
-        commands.spawn((
-            Monster,
-            MonsterAI {
-                target_player: false,
-                random_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
-                random_dir: Vec2::ZERO,
-                action_timer: Timer::from_seconds(0.1, TimerMode::Once),
-                action_cooldown: Timer::from_seconds(2.0, TimerMode::Once),
-                health: 100.0,
-                last_health: 100.0,
-                stun_cooldown: Timer::from_seconds(0.375, TimerMode::Once),
-            },
-            RenderLayers::from_layers(CAMERA_LAYER_EFFECT),
-            Pending,
-            Mesh2d(meshes.add(Rectangle::new(40.0, 42.5))),
-            Transform::from_xyz(pos.x, pos.y, -32.0),
-            children![(
-                Camera2d,
-                Camera {
-                    order: -100,
-                    clear_color: ClearColorConfig::None,
-                    ..default()
+        spawn_monster(&mut commands, &asset_server, &mut texture_atlas_layouts, &mut meshes, &atlas_handles, pos, 100.0);
+    }
+}
+
+/// Potwór w pozycji świata
+pub fn spawn_monster(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    meshes: &mut Assets<Mesh>,
+    atlas_handles: &AtlasHandles,
+    pos: Vec2,
+    health: f32,
+) -> Entity {
+    let texture = asset_server.load("textures/monster_combined.png");
+    let layout = TextureAtlasLayout::from_grid(bevy::prelude::UVec2::splat(64), 2, 5, None, None);
+    let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let monster_animation_indices = atlas_handles.0.get("walk").unwrap().clone();
+    commands.spawn((
+        Monster,
+        MonsterAI {
+            target_player: false,
+            sees_player: false,
+            random_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
+            random_dir: Vec2::ZERO,
+            action_timer: Timer::from_seconds(0.1, TimerMode::Once),
+            action_cooldown: Timer::from_seconds(2.0, TimerMode::Once),
+            health,
+        },
+        KnockbackProps { mass: 1.0, resistance: 0.0 },
+        RenderLayers::from_layers(CAMERA_LAYER_EFFECT),
+        Pending,
+        PhysicsBody::Kinematic,
+        CharacterController::default(),
+        BodyShape::Cuboid { half_extents: Vec2::new(20.0, 21.25) },
+        CollisionLayer::Monster,
+        Mesh2d(meshes.add(Rectangle::new(40.0, 42.5))),
+        Transform::from_xyz(pos.x, pos.y, -32.0),
+        children![(
+            Sprite::from_atlas_image(
+                texture.clone(),
+                bevy::prelude::TextureAtlas {
+                    layout: texture_atlas_layout.clone(),
+                    index: monster_animation_indices.first,
                 },
-                RenderTarget::Image(ImageRenderTarget::from(image_handle.clone())),
-                RenderLayers::from_layers(CAMERA_LAYER_MONSTER),
-                AICamera,
             ),
-            (
-                Sprite::from_atlas_image(
-                    texture.clone(),
-                    bevy::prelude::TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
-                        index: monster_animation_indices.first,
-                    },
-                ),
-                YSort { z: 0.375 },
-                Transform::from_xyz(0.0, 37.5, 64.0).with_scale(Vec3::splat(2.0)),
-                RenderLayers::from_layers(CAMERA_LAYER_MONSTER),
-                monster_animation_indices,
-                AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
-                MonsterSprite,
-                AttackStatus(false),
-                FinishStatus(false),
-            ),(
-                Transform::from_xyz(0.0, 15.0, 0.0),
-                PointLight2d {
-                    range: 375.0,
-                    intensity: 0.075,
-                    color: Color::srgba(1.0, 0.5, 0.0, 1.0),
+            YSort { z: 0.375 },
+            Transform::from_xyz(0.0, 37.5, 64.0).with_scale(Vec3::splat(2.0)),
+            RenderLayers::from_layers(CAMERA_LAYER_MONSTER),
+            monster_animation_indices,
+            AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
+            MonsterSprite,
+            AttackStatus(false),
+            FinishStatus(false),
+        ),(
+            Transform::from_xyz(0.0, 15.0, 0.0),
+            LightSource {
+                range: 375.0,
+                intensity: 0.075,
+                color: Color::srgba(1.0, 0.5, 0.0, 1.0),
+            },
+            YSort { z: 0.0 },
+        )],
+    )).id()
+}
+
+/// Kamera AI z obrazem jako celem; pierwszy potwór trafia na podgląd debugowy w HUD
+fn attach_ai_camera(
+    mut commands: Commands,
+    monsters: Query<(Entity, &Transform), Added<Monster>>,
+    mut images: ResMut<Assets<Image>>,
+    menu_root_query: Query<Entity, (With<HealthBar>, Without<DebugAI>)>,
+) {
+    let mut preview_root = menu_root_query.iter().next();
+    for (monster, transform) in &monsters {
+        let image_handle = create_ai_texture(&mut images, 1024, 1024);
+        let camera = commands.spawn((
+            Camera2d,
+            Camera {
+                order: -100,
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            RenderTarget::Image(ImageRenderTarget::from(image_handle.clone())),
+            RenderLayers::from_layers(CAMERA_LAYER_MONSTER),
+            // potwór odwrócony w lewo - kamera nie może odbijać obrazu
+            Transform::from_scale(Vec3::new(transform.scale.x.signum(), 1.0, 1.0)),
+            AICamera,
+        )).id();
+        commands.entity(monster).add_child(camera);
+
+        if let Some(root) = preview_root.take() {
+            let child = commands.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(120.0),   // 1/5 szerokości
+                    height: Val::Percent(1200.0),  // 1/5 wysokości
+                    right: Val::Px(-1500.0),        // prawy róg
+                    bottom: Val::Px(-450.0),
                     ..default()
                 },
-                YSort { z: 0.0 },
-            )],
-        ));
-        if spawned == false {
-            for root in menu_root_query.iter() {
-                if spawned == true {
-                    break;
-                }
-                let child = commands.spawn((
-                    Node {
-                        position_type: PositionType::Absolute,
-                        width: Val::Percent(120.0),   // 1/5 szerokości
-                        height: Val::Percent(1200.0),  // 1/5 wysokości
-                        right: Val::Px(-1500.0),        // prawy róg
-                        bottom: Val::Px(-450.0),
-                        ..default()
-                    },
-                    ImageNode::new(image_handle.clone()),
-                )).id();
-                commands.entity(root).add_children(&[child]);
-                commands.entity(root).insert(DebugAI);
-                println!("Spawning monster at ({}, {})", pos.x, pos.y);
-                spawned = true;
-            }
+                ImageNode::new(image_handle),
+            )).id();
+            commands.entity(root).add_children(&[child]);
+            commands.entity(root).insert(DebugAI);
         }
     }
 }
@@ -263,39 +304,35 @@ fn create_ai_texture(
 
 fn monster_ai(
     time: Res<Time>,
-    mut player_query: Query<(&Transform, &mut PlayerData), (With<Player>, Without<Pending>)>,
-    mut query: Query<(&mut MonsterAI, &mut RigidBodyHandleComponent, &mut Transform, Entity, &Children), (With<Monster>, Without<Player>, Without<Pending>)>,
+    mut player_query: Query<(Entity, &Transform, &mut PlayerData), (With<Player>, Without<Pending>)>,
+    mut query: Query<(&mut MonsterAI, &RigidBodyHandleComponent, &mut CharacterMovement, Has<Stunned>, &mut Transform, Entity, &Children), (With<Monster>, Without<Player>, Without<Pending>)>,
     mut child_query: Query<(&mut AnimationIndices, &mut AttackStatus, &mut FinishStatus, &mut Sprite), With<MonsterSprite>>,
-    mut rigid_bodies: ResMut<ResRigidBodySet>,
-    mut colliders: ResMut<ResColliderSet>,
-    mut island_manager: ResMut<ResIslandManager>,
+    rigid_bodies: Res<ResRigidBodySet>,
     mut commands: Commands,
-    mut query_ui: Query<(&mut Text, &mut PointText), With<PointText>>,
+    mut score: ResMut<Score>,
     config: Res<MonsterConfig>,
     atlas_handles: Res<AtlasHandles>,
     bodies_query: Query<(&Transform), (Or<(With<Wall>, With<Floor>)>, Without<Pending>, With<RigidBodyHandleComponent>, Without<Player>, Without<Monster>)>,
     mut camera_query: Query<&mut Transform, (With<AICamera>, With<Camera2d>, Without<PlayerCamera>,Without<Player>, Without<RigidBodyHandleComponent>, Without<Wall>, Without<Floor>)>,
+    terrain_map: Res<TerrainMap>,
+    mut knockback: MessageWriter<ApplyKnockback>,
+    settings: Res<Settings>,
 ) {
-    let (player_transform, mut player_data_some): (Transform, Option<Mut<PlayerData>>) =
-    if let Ok((t, mut d)) = player_query.single_mut() {
-        (t.clone(), Some(d)) // <- klonujemy Transform, żeby mieć wartość
+    let (player_entity, player_transform, mut player_data_some): (Option<Entity>, Transform, Option<Mut<PlayerData>>) =
+    if let Ok((e, t, mut d)) = player_query.single_mut() {
+        (Some(e), t.clone(), Some(d)) // <- klonujemy Transform, żeby mieć wartość
     } else {
-        (Transform::default(), None)
-    };
-
-    let Ok((mut t_ui, mut t_pt)) = query_ui.single_mut() else {
-        return;
+        (None, Transform::default(), None)
     };
 
     let tile_size = TILE_SIZE;
-    let see_distance = 5.0 * tile_size;
     let forget_distance = 10.0 * tile_size;
     let despawn_distance = config.max_despawn_distance * tile_size;
     let action_distance = 1.25 * tile_size;
     let speed = 80.0; // wolniejsze
 
-    for (mut ai, rb_handle, mut rb_transform, entity, children) in &mut query {
-        if let Some(rigid_body) = rigid_bodies.0.get_mut(rb_handle.0) {
+    for (mut ai, rb_handle, mut movement, stunned, mut rb_transform, entity, children) in &mut query {
+        if let Some(rigid_body) = rigid_bodies.0.get(rb_handle.0) {
             let monster_pos = Vec2::new(
                 rigid_body.position().translation.x,
                 rigid_body.position().translation.y,
@@ -305,24 +342,6 @@ fn monster_ai(
 
             // despawn jeśli zbyt daleko
             if distance > despawn_distance {
-                let mut colliders_clone = Vec::new();
-                if let Some(rb) = rigid_bodies.0.get(rb_handle.0) {
-                    for collider_handle in rb.colliders() {
-                        colliders_clone.push(collider_handle.clone());
-                    }
-                }
-
-                for collider_handle in colliders_clone {
-                    colliders.0.remove(collider_handle, &mut island_manager.0, &mut rigid_bodies.0, true);
-                }
-                rigid_bodies.0.remove(
-                    rb_handle.0,
-                    &mut island_manager.0,
-                    &mut colliders.0,
-                    &mut ImpulseJointSet::new(),
-                    &mut MultibodyJointSet::new(),
-                    true, // usuwa powiązane collidery
-                );
                 commands.entity(entity).despawn();
                 continue;
             }
@@ -340,30 +359,13 @@ fn monster_ai(
             }
             if !next || ai.health <= 0.0 {
                 if ai.health <= 0.0 {
-                    t_pt.0 += 1;
-                    t_ui.0 = format!("Points: {}", t_pt.0);
-                }
-                let mut colliders_clone = Vec::new();
-                if let Some(rb) = rigid_bodies.0.get(rb_handle.0) {
-                    for collider_handle in rb.colliders() {
-                        colliders_clone.push(collider_handle.clone());
-                    }
-                }
-
-                for collider_handle in colliders_clone {
-                    colliders.0.remove(collider_handle, &mut island_manager.0, &mut rigid_bodies.0, true);
+                    score.0 += 1;
                 }
-                rigid_bodies.0.remove(
-                    rb_handle.0,
-                    &mut island_manager.0,
-                    &mut colliders.0,
-                    &mut ImpulseJointSet::new(),
-                    &mut MultibodyJointSet::new(),
-                    true, // usuwa powiązane collidery
-                );
                 commands.entity(entity).despawn();
                 continue;
             }
+            // sprite szukamy po komponencie - kolejność dzieci nie jest gwarantowana
+            let sprite_child = children.iter().find(|child| child_query.contains(*child));
             // AI logika
             if ai.target_player {
                 if let Some(ref mut player_data) = player_data_some {
@@ -373,8 +375,8 @@ fn monster_ai(
                         ai.random_timer.reset();
                         ai.action_timer.reset();
                     } else if distance < action_distance {
-                        if let Ok((mut child_indices, mut attack, mut finish, mut sprite)) = child_query.get_mut(children[0]) {
-                            if attack.0 == false && finish.0 == false && ai.action_cooldown.just_finished() {
+                        if let Some(Ok((mut child_indices, mut attack, mut finish, mut sprite))) = sprite_child.map(|child| child_query.get_mut(child)) {
+                            if attack.0 == false && finish.0 == false && !stunned && ai.action_cooldown.just_finished() {
                                 attack.0 = true;
                                 ai.action_cooldown.reset();
                                 ai.action_timer.reset();
@@ -387,8 +389,16 @@ fn monster_ai(
                             if finish.0 {
                                 finish.0 = false;
                                 ai.action_cooldown.reset();
-                                player_data.damage(20.0);
+                                player_data.damage(MONSTER_HIT_DAMAGE * settings.difficulty.damage_multiplier());
                                 player_data.can_heal.reset();
+                                if let Some(player) = player_entity {
+                                    knockback.write(ApplyKnockback {
+                                        target: player,
+                                        direction: player_pos - monster_pos,
+                                        impulse: MONSTER_HIT_KNOCKBACK,
+                                        stun: MONSTER_HIT_STUN,
+                                    });
+                                }
                             }
                         }
                         /*ai.action_timer.tick(time.delta());
@@ -399,7 +409,7 @@ fn monster_ai(
                             ai.action_timer.reset();
                         }*/
                     } else {
-                        if let Ok((mut child_indices, mut attack, mut finish, mut sprite)) = child_query.get_mut(children[0]) {
+                        if let Some(Ok((_, _, mut finish, _))) = sprite_child.map(|child| child_query.get_mut(child)) {
                             finish.0 = false;
                         }
                         ai.action_timer.reset();
@@ -411,7 +421,7 @@ fn monster_ai(
                     ai.action_timer.reset();
                 }
             } else {
-                if distance < see_distance && player_data_some.is_some() {
+                if ai.sees_player && player_data_some.is_some() {
                     ai.target_player = true;
                 }
             }
@@ -428,33 +438,27 @@ fn monster_ai(
                 }
                 ai.random_dir
             };
+            // omijamy lawę, bagna i ciernie jeśli się da
+            let dir = avoid_hazards(&terrain_map, monster_pos, dir);
+            let speed = match terrain_map.hazard_at(monster_pos) {
+                Some(kind) => speed * kind.speed_multiplier(),
+                None => speed,
+            };
 
-            let mut velocity = dir * speed;
-            if ai.health < ai.last_health {
-                // obrażenia, cofamy się
-                velocity = -dir * speed * 3.14/2.0;
-                ai.last_health = (ai.health*2.0+ai.last_health)/3.0;
-                rigid_body.set_linvel(vector![velocity.x, velocity.y], true);
-            } else {
-                ai.last_health = ai.health;
-                if ai.stun_cooldown.just_finished() {
-                    rigid_body.set_linvel(vector![velocity.x, velocity.y], true);
-                } else {
-                    ai.stun_cooldown.tick(time.delta());
-                }
-            }
+            // ogłuszony potwór nie steruje - porusza nim tylko odrzut
+            movement.velocity = if stunned { Vec2::ZERO } else { dir * speed };
             //rigid_body.set_linvel(vector![velocity.x, velocity.y], true);
             //rigid_body.lock_rotations(true, true);
             //rigid_body.set_body_type(RigidBodyType::Dynamic, true);
             if dir.x < 0.0 {
                 if rb_transform.scale.x < 0.0 {
                     rb_transform.scale.x *= -1.0;
-                    camera_query.get_mut(children[0]).unwrap().scale.x *= -1.0;
+                    flip_ai_camera(children, &mut camera_query);
                 }
             } else {
                 if rb_transform.scale.x > 0.0 {
                     rb_transform.scale.x *= -1.0;
-                    camera_query.get_mut(children[0]).unwrap().scale.x *= -1.0;
+                    flip_ai_camera(children, &mut camera_query);
                 }
             }
             //rb_transform.translation.z = -(((WORLD_SIZE as f32*TILE_SIZE)/2.0)/64.0 + rigid_body.translation().y.round()/64.0) + 64.0;
@@ -462,6 +466,40 @@ fn monster_ai(
     }
 }
 
+/// Kamera AI odwraca się razem z potworem, żeby jej obraz nie był lustrzany (bez renderowania jej nie ma)
+fn flip_ai_camera(
+    children: &Children,
+    camera_query: &mut Query<&mut Transform, (With<AICamera>, With<Camera2d>, Without<PlayerCamera>,Without<Player>, Without<RigidBodyHandleComponent>, Without<Wall>, Without<Floor>)>,
+) {
+    for child in children.iter() {
+        if let Ok(mut camera) = camera_query.get_mut(child) {
+            camera.scale.x *= -1.0;
+        }
+    }
+}
+
+/// Wzrok potworów: zasięg + promień do gracza, który nie może przecinać ścian
+fn monster_sight(
+    player_query: Query<&Transform, (With<Player>, Without<Pending>)>,
+    mut query: Query<(Entity, &mut MonsterAI, &Transform), (With<Monster>, Without<Player>, Without<Pending>)>,
+    physics_query: PhysicsQuery,
+) {
+    let see_distance = 5.0 * TILE_SIZE;
+    let Ok(player_transform) = player_query.single() else {
+        for (_, mut ai, _) in &mut query {
+            ai.sees_player = false;
+        }
+        return;
+    };
+    let player_pos = player_transform.translation.xy();
+    for (entity, mut ai, transform) in &mut query {
+        let monster_pos = transform.translation.xy();
+        let walls = PhysicsQueryFilter::layers(&[CollisionLayer::Wall]).excluding(entity);
+        ai.sees_player = monster_pos.distance(player_pos) < see_distance
+            && physics_query.line_of_sight(monster_pos, player_pos, walls);
+    }
+}
+
 fn rand_dir() -> f32 {
     // losowa wartość między -1 a 1
     (rand::random::<f32>() - 0.5) * 2.0
@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use crate::resourses::physics_resources::*;
+use crate::systems::menu_ui::{spawn_button, MenuAssets};
+use crate::systems::settings::{Difficulty, Language, Settings, WindowModeSetting, RESOLUTIONS};
+
+pub struct OptionsPlugin;
+
+const VOLUME_STEP: f32 = 0.1;
+const UI_SCALE_STEP: f32 = 0.25;
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 2.0;
+
+/// Wiersz ekranu opcji
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OptionField {
+    WindowMode,
+    Resolution,
+    VSync,
+    UiScale,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    Language,
+    Difficulty,
+}
+
+const FIELDS: [(OptionField, &str); 9] = [
+    (OptionField::WindowMode, "Window mode"),
+    (OptionField::Resolution, "Resolution"),
+    (OptionField::VSync, "VSync"),
+    (OptionField::UiScale, "UI scale"),
+    (OptionField::MasterVolume, "Master volume"),
+    (OptionField::MusicVolume, "Music volume"),
+    (OptionField::SfxVolume, "SFX volume"),
+    (OptionField::Language, "Language"),
+    (OptionField::Difficulty, "Difficulty"),
+];
+
+/// Przycisk "<" / ">" zmieniający wartość opcji
+#[derive(Component)]
+struct OptionButton {
+    field: OptionField,
+    step: i32,
+}
+
+#[derive(Component)]
+struct OptionValue(OptionField);
+
+#[derive(Component)]
+enum OptionsNavButton {
+    Controls,
+    Back,
+}
+
+#[derive(Component)]
+struct OptionsRoot;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuScreen::Options), spawn_options)
+            .add_systems(Update, (
+                option_buttons,
+                nav_buttons,
+                refresh_options.run_if(resource_changed::<Settings>),
+            ).chain().run_if(in_state(MenuScreen::Options)));
+    }
+}
+
+/// Następny/poprzedni element listy (z zawijaniem)
+fn cycle<T: Copy + PartialEq>(values: &[T], current: T, step: i32) -> T {
+    let index = values.iter().position(|v| *v == current).unwrap_or(0) as i32;
+    values[(index + step).rem_euclid(values.len() as i32) as usize]
+}
+
+fn step_volume(volume: f32, step: i32) -> f32 {
+    // zaokrąglenie, żeby 0.1 * n nie zbierało błędów
+    ((volume + step as f32 * VOLUME_STEP).clamp(0.0, 1.0) * 10.0).round() / 10.0
+}
+
+fn change(settings: &mut Settings, field: OptionField, step: i32) {
+    match field {
+        OptionField::WindowMode => {
+            settings.window_mode = cycle(
+                &[WindowModeSetting::Windowed, WindowModeSetting::Borderless, WindowModeSetting::Fullscreen],
+                settings.window_mode,
+                step,
+            );
+        }
+        OptionField::Resolution => settings.resolution = cycle(&RESOLUTIONS, settings.resolution, step),
+        OptionField::VSync => settings.vsync = !settings.vsync,
+        OptionField::UiScale => {
+            settings.ui_scale = (settings.ui_scale + step as f32 * UI_SCALE_STEP).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+        }
+        OptionField::MasterVolume => settings.master_volume = step_volume(settings.master_volume, step),
+        OptionField::MusicVolume => settings.music_volume = step_volume(settings.music_volume, step),
+        OptionField::SfxVolume => settings.sfx_volume = step_volume(settings.sfx_volume, step),
+        OptionField::Language => settings.language = cycle(&[Language::English, Language::Polish], settings.language, step),
+        OptionField::Difficulty => {
+            settings.difficulty = cycle(&[Difficulty::Easy, Difficulty::Normal, Difficulty::Hard], settings.difficulty, step);
+        }
+    }
+}
+
+fn value_text(settings: &Settings, field: OptionField) -> String {
+    let language = settings.language;
+    match field {
+        OptionField::WindowMode => language.text(match settings.window_mode {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::Borderless => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }).to_string(),
+        OptionField::Resolution => format!("{}x{}", settings.resolution[0], settings.resolution[1]),
+        OptionField::VSync => language.text(if settings.vsync { "On" } else { "Off" }).to_string(),
+        OptionField::UiScale => format!("{:.2}x", settings.ui_scale),
+        OptionField::MasterVolume => format!("{:.0}%", settings.master_volume * 100.0),
+        OptionField::MusicVolume => format!("{:.0}%", settings.music_volume * 100.0),
+        OptionField::SfxVolume => format!("{:.0}%", settings.sfx_volume * 100.0),
+        OptionField::Language => match settings.language {
+            Language::English => "English".to_string(),
+            Language::Polish => "Polski".to_string(),
+        },
+        OptionField::Difficulty => language.text(match settings.difficulty {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }).to_string(),
+    }
+}
+
+fn spawn_options(mut commands: Commands, menu_assets: Res<MenuAssets>, settings: Res<Settings>) {
+    build_options(&mut commands, &menu_assets, &settings);
+}
+
+fn build_options(commands: &mut Commands, menu_assets: &MenuAssets, settings: &Settings) {
+    let font = menu_assets.font.clone();
+    let language = settings.language;
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        ImageNode::new(menu_assets.background.clone()),
+        MenuRoot,
+        OptionsRoot,
+        DespawnOnExit(MenuScreen::Options),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new(language.text("Options")),
+            TextFont { font: font.clone(), font_size: 40.0, ..default() },
+            TextColor(Color::WHITE),
+            Node { margin: UiRect::bottom(Val::Px(20.0)), ..default() },
+        ));
+
+        for (field, label) in FIELDS {
+            parent.spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|row| {
+                row.spawn((
+                    Text::new(language.text(label)),
+                    TextFont { font: font.clone(), font_size: 22.0, ..default() },
+                    TextColor(Color::WHITE),
+                    Node { width: Val::Px(260.0), ..default() },
+                ));
+                spawn_button(row, &font, "<", OptionButton { field, step: -1 });
+                row.spawn((
+                    Text::new(value_text(settings, field)),
+                    TextFont { font: font.clone(), font_size: 22.0, ..default() },
+                    TextColor(Color::WHITE),
+                    TextLayout::new_with_justify(Justify::Center),
+                    Node { width: Val::Px(200.0), ..default() },
+                    OptionValue(field),
+                ));
+                spawn_button(row, &font, ">", OptionButton { field, step: 1 });
+            });
+        }
+
+        parent.spawn(Node { margin: UiRect::top(Val::Px(20.0)), ..default() })
+            .with_children(|row| {
+                spawn_button(row, &font, language.text("Controls"), OptionsNavButton::Controls);
+                spawn_button(row, &font, language.text("Back"), OptionsNavButton::Back);
+            });
+    });
+}
+
+fn option_buttons(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &OptionButton), Changed<Interaction>>,
+    mut settings: ResMut<Settings>,
+    menu_assets: Res<MenuAssets>,
+    roots: Query<Entity, With<OptionsRoot>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        change(&mut settings, button.field, button.step);
+        // zmiana języka przebudowuje cały ekran
+        if button.field == OptionField::Language {
+            for root in &roots {
+                commands.entity(root).despawn();
+            }
+            build_options(&mut commands, &menu_assets, &settings);
+        }
+    }
+}
+
+fn nav_buttons(
+    interaction_query: Query<(&Interaction, &OptionsNavButton), Changed<Interaction>>,
+    mut next_screen: ResMut<NextState<MenuScreen>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        next_screen.set(match button {
+            OptionsNavButton::Controls => MenuScreen::Controls,
+            OptionsNavButton::Back => MenuScreen::Main,
+        });
+    }
+}
+
+fn refresh_options(
+    settings: Res<Settings>,
+    mut values: Query<(&OptionValue, &mut Text)>,
+) {
+    for (value, mut text) in &mut values {
+        text.0 = value_text(&settings, value.0);
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use rapier2d::prelude::*;
 use crate::resourses::physics_resources::*;
 
@@ -19,9 +20,192 @@ impl Plugin for PhysicsPlugin {
         app.insert_resource(ResMultibodyJointSet(MultibodyJointSet::new()));
         app.insert_resource(ResCCDSolver(CCDSolver::new()));
         app.insert_resource(ResQueryPipeline(QueryPipeline::new()));
+        app.init_resource::<ResEventCollector>();
+        app.insert_resource(PhysicsEntityMap::default());
+        app.insert_resource(ProjectileOwners::default());
+        app.init_resource::<PhysicsTimestep>();
+        app.init_resource::<SimulationControl>();
+        app.add_message::<CollisionStarted>();
+        app.add_message::<CollisionStopped>();
+        app.add_message::<ContactForce>();
+        app.add_message::<CharacterCollided>();
+        app.add_observer(cleanup_rigid_body);
         app.add_systems(Startup, init_physics);
-        app.add_systems(FixedUpdate, step_physics);
+        app.add_systems(PreUpdate, apply_simulation_control);
+        app.add_systems(Update, run_single_steps);
+        app.add_systems(FixedUpdate, (move_characters, step_physics, forward_collision_events, prune_entity_map).chain()
+            .run_if(in_state(AppState::Playing)));
         app.add_systems(FixedPostUpdate, sync_physics_to_transform);
+        app.add_systems(Update, apply_physics_timestep.run_if(resource_changed::<PhysicsTimestep>));
+        app.add_systems(PostUpdate, interpolate_transforms.before(TransformSystems::Propagate));
+    }
+}
+
+/// Filtr zapytań fizyki: trafiane warstwy, pomijana encja i sensory
+#[derive(Clone, Copy, Default)]
+pub struct PhysicsQueryFilter {
+    /// None = wszystkie warstwy
+    pub layers: Option<Group>,
+    pub exclude: Option<Entity>,
+    pub include_sensors: bool,
+}
+
+impl PhysicsQueryFilter {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn layers(layers: &[CollisionLayer]) -> Self {
+        Self {
+            layers: Some(layers.iter().fold(Group::NONE, |acc, layer| acc | layer.group())),
+            ..default()
+        }
+    }
+
+    pub fn excluding(mut self, entity: Entity) -> Self {
+        self.exclude = Some(entity);
+        self
+    }
+
+    pub fn with_sensors(mut self) -> Self {
+        self.include_sensors = true;
+        self
+    }
+}
+
+/// Zapytania do ResQueryPipeline zwracające encje zamiast uchwytów rapiera.
+/// Pipeline aktualizuje się w step_physics, więc wyniki są z ostatniego kroku
+#[derive(SystemParam)]
+pub struct PhysicsQuery<'w> {
+    query_pipeline: Res<'w, ResQueryPipeline>,
+    rigid_bodies: Res<'w, ResRigidBodySet>,
+    colliders: Res<'w, ResColliderSet>,
+    entity_map: Res<'w, PhysicsEntityMap>,
+}
+
+impl PhysicsQuery<'_> {
+    fn query_filter(&self, filter: &PhysicsQueryFilter) -> QueryFilter<'static> {
+        let mut query_filter = QueryFilter::new();
+        if let Some(layers) = filter.layers {
+            query_filter = query_filter.groups(InteractionGroups::new(Group::ALL, layers));
+        }
+        if !filter.include_sensors {
+            query_filter = query_filter.exclude_sensors();
+        }
+        if let Some(body) = filter.exclude.and_then(|entity| self.entity_map.body_of(entity)) {
+            query_filter = query_filter.exclude_rigid_body(body);
+        }
+        query_filter
+    }
+
+    /// Pierwsza encja na promieniu i odległość do niej
+    pub fn raycast(&self, origin: Vec2, dir: Vec2, max_distance: f32, filter: PhysicsQueryFilter) -> Option<(Entity, f32)> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec2::ZERO {
+            return None;
+        }
+        let ray = Ray::new(point![origin.x, origin.y], vector![dir.x, dir.y]);
+        let (handle, distance) = self.query_pipeline.0.cast_ray(
+            &self.rigid_bodies.0,
+            &self.colliders.0,
+            &ray,
+            max_distance,
+            true,
+            self.query_filter(&filter),
+        )?;
+        Some((self.entity_map.entity_of_collider(handle)?, distance))
+    }
+
+    /// Czy odcinek from -> to nie jest przecięty przez nic z filtra (np. ściany)
+    pub fn line_of_sight(&self, from: Vec2, to: Vec2, filter: PhysicsQueryFilter) -> bool {
+        self.raycast(from, to - from, from.distance(to), filter).is_none()
+    }
+
+    /// Przesuwa kształt z origin w kierunku dir; pierwsza trafiona encja i przebyta odległość
+    pub fn shape_cast(
+        &self,
+        shape: &BodyShape,
+        origin: Vec2,
+        dir: Vec2,
+        max_distance: f32,
+        filter: PhysicsQueryFilter,
+    ) -> Option<(Entity, f32)> {
+        let shape = shape.to_shared_shape()?;
+        let dir = dir.normalize_or_zero();
+        if dir == Vec2::ZERO {
+            return None;
+        }
+        let options = rapier2d::parry::query::ShapeCastOptions {
+            max_time_of_impact: max_distance,
+            target_distance: 0.0,
+            stop_at_penetration: true,
+            compute_impact_geometry_on_penetration: false,
+        };
+        let (handle, hit) = self.query_pipeline.0.cast_shape(
+            &self.rigid_bodies.0,
+            &self.colliders.0,
+            &Isometry::translation(origin.x, origin.y),
+            &vector![dir.x, dir.y],
+            &*shape,
+            options,
+            self.query_filter(&filter),
+        )?;
+        Some((self.entity_map.entity_of_collider(handle)?, hit.time_of_impact))
+    }
+
+    /// Encje, których collidery nachodzą na kształt ustawiony w pos
+    pub fn intersect_shape(&self, shape: &BodyShape, pos: Vec2, filter: PhysicsQueryFilter) -> Vec<Entity> {
+        let Some(shape) = shape.to_shared_shape() else {
+            return Vec::new();
+        };
+        let mut found = Vec::new();
+        self.query_pipeline.0.intersections_with_shape(
+            &self.rigid_bodies.0,
+            &self.colliders.0,
+            &Isometry::translation(pos.x, pos.y),
+            &*shape,
+            self.query_filter(&filter),
+            |handle| {
+                found.extend(self.entity_map.entity_of_collider(handle));
+                true
+            },
+        );
+        found
+    }
+
+    /// Encje, których collidery zawierają punkt
+    pub fn intersect_point(&self, point: Vec2, filter: PhysicsQueryFilter) -> Vec<Entity> {
+        let mut found = Vec::new();
+        self.query_pipeline.0.intersections_with_point(
+            &self.rigid_bodies.0,
+            &self.colliders.0,
+            &point![point.x, point.y],
+            self.query_filter(&filter),
+            |handle| {
+                found.extend(self.entity_map.entity_of_collider(handle));
+                true
+            },
+        );
+        found
+    }
+
+    /// Encje, których AABB colliderów nachodzą na prostokąt min..max
+    pub fn intersect_aabb(&self, min: Vec2, max: Vec2, filter: PhysicsQueryFilter) -> Vec<Entity> {
+        let aabb = Aabb::new(point![min.x, min.y], point![max.x, max.y]);
+        let query_filter = self.query_filter(&filter);
+        let mut found = Vec::new();
+        self.query_pipeline.0.colliders_with_aabb_intersecting_aabb(&aabb, |handle| {
+            let passes = self
+                .colliders
+                .0
+                .get(*handle)
+                .is_some_and(|collider| query_filter.test(&self.rigid_bodies.0, *handle, collider));
+            if passes {
+                found.extend(self.entity_map.entity_of_collider(*handle));
+            }
+            true
+        });
+        found
     }
 }
 
@@ -31,6 +215,68 @@ fn init_physics(
     physics_work.0 = true;
 }
 
+/// Przesuwa kinematyczne postacie kontrolerem rapiera (ślizganie po ścianach, progi, dociąganie)
+fn move_characters(
+    mut characters: Query<(
+        Entity,
+        &CharacterController,
+        &CharacterMovement,
+        Option<&Knockback>,
+        &mut CharacterOutput,
+        &RigidBodyHandleComponent,
+        &ColliderComponent,
+    )>,
+    mut rigid_bodies: ResMut<ResRigidBodySet>,
+    colliders: Res<ResColliderSet>,
+    query_pipeline: Res<ResQueryPipeline>,
+    integration_parameters: Res<ResIntegrationParameters>,
+    entity_map: Res<PhysicsEntityMap>,
+    mut collided: MessageWriter<CharacterCollided>,
+) {
+    let dt = integration_parameters.0.dt;
+    for (entity, controller, movement, knockback, mut output, rb_handle, col_handle) in &mut characters {
+        let (Some(body), Some(collider)) = (rigid_bodies.0.get(rb_handle.0), colliders.0.get(col_handle.0)) else {
+            continue;
+        };
+        let position = *body.position();
+        let knockback = knockback.map(|k| k.0).unwrap_or(Vec2::ZERO);
+        let desired = (movement.velocity + knockback) * dt;
+        let filter = QueryFilter::new()
+            .exclude_rigid_body(rb_handle.0)
+            .exclude_sensors()
+            .groups(collider.collision_groups());
+
+        let mut collisions = Vec::new();
+        let effective = controller.to_rapier().move_shape(
+            dt,
+            &rigid_bodies.0,
+            &colliders.0,
+            &query_pipeline.0,
+            collider.shape(),
+            &position,
+            vector![desired.x, desired.y],
+            filter,
+            |collision| collisions.push(collision),
+        );
+
+        output.translation = Vec2::new(effective.translation.x, effective.translation.y);
+        output.grounded = effective.grounded;
+        output.hits.clear();
+        for collision in collisions {
+            let Some(other) = entity_map.entity_of_collider(collision.handle) else {
+                continue;
+            };
+            output.hits.push(other);
+            let normal = collision.hit.normal1;
+            collided.write(CharacterCollided { character: entity, other, normal: Vec2::new(normal.x, normal.y) });
+        }
+
+        if let Some(body) = rigid_bodies.0.get_mut(rb_handle.0) {
+            body.set_next_kinematic_translation(position.translation.vector + effective.translation);
+        }
+    }
+}
+
 fn step_physics(
     mut pipeline: ResMut<ResPhysicsPipeline>,
     mut query_pipeline: ResMut<ResQueryPipeline>,
@@ -44,7 +290,11 @@ fn step_physics(
     mut narrow_phase: ResMut<ResNarrowPhase>,
     mut ccd_solver: ResMut<ResCCDSolver>,
     gravity: Res<ResGravity>,
+    events: Res<ResEventCollector>,
+    owners: Res<ProjectileOwners>,
+    entity_map: Res<PhysicsEntityMap>,
 ) {
+    let hooks = OwnerFilterHooks { owners: &owners.0, entity_map: &entity_map };
     pipeline.0.step(
         &gravity.0,
         &integration_parameters.0,
@@ -57,20 +307,166 @@ fn step_physics(
         &mut multibody_joints.0,
         &mut ccd_solver.0,
         Some(&mut query_pipeline.0),
-        &(),
-        &(),
+        &hooks,
+        &events.collector,
     );
 }
 
+/// Przekazuje zdarzenia kolizji z rapiera jako wiadomości Bevy
+fn forward_collision_events(
+    events: Res<ResEventCollector>,
+    entity_map: Res<PhysicsEntityMap>,
+    mut started: MessageWriter<CollisionStarted>,
+    mut stopped: MessageWriter<CollisionStopped>,
+    mut forces: MessageWriter<ContactForce>,
+) {
+    // mapa działa także dla colliderów już usuniętych (zdarzenia REMOVED)
+    let entity_of = |handle: ColliderHandle| entity_map.entity_of_collider(handle);
+
+    while let Ok(event) = events.collision_recv.try_recv() {
+        let (Some(a), Some(b)) = (entity_of(event.collider1()), entity_of(event.collider2())) else {
+            continue;
+        };
+        match event {
+            CollisionEvent::Started(_, _, _) => {
+                started.write(CollisionStarted { a, b, sensor: event.sensor() });
+            }
+            CollisionEvent::Stopped(_, _, _) => {
+                stopped.write(CollisionStopped { a, b, sensor: event.sensor() });
+            }
+        }
+    }
+
+    while let Ok(event) = events.contact_force_recv.try_recv() {
+        let (Some(a), Some(b)) = (entity_of(event.collider1), entity_of(event.collider2)) else {
+            continue;
+        };
+        forces.write(ContactForce { a, b, total_force_magnitude: event.total_force_magnitude });
+    }
+}
+
+/// Zbiory rapiera potrzebne do usunięcia ciała
+#[derive(SystemParam)]
+pub struct BodyRemover<'w> {
+    rigid_bodies: ResMut<'w, ResRigidBodySet>,
+    colliders: ResMut<'w, ResColliderSet>,
+    island_manager: ResMut<'w, ResIslandManager>,
+    impulse_joints: ResMut<'w, ResImpulseJointSet>,
+    multibody_joints: ResMut<'w, ResMultibodyJointSet>,
+    owners: ResMut<'w, ProjectileOwners>,
+}
+
+impl BodyRemover<'_> {
+    fn remove(&mut self, entity: Entity, handle: RigidBodyHandle) {
+        self.owners.0.remove(&entity);
+        self.rigid_bodies.0.remove(
+            handle,
+            &mut self.island_manager.0,
+            &mut self.colliders.0,
+            &mut self.impulse_joints.0,
+            &mut self.multibody_joints.0,
+            true, // usuwa powiązane collidery
+        );
+    }
+}
+
+/// Usuwa ciało (razem z colliderami) z rapiera, gdy encja traci RigidBodyHandleComponent, np. przy despawnie
+fn cleanup_rigid_body(
+    remove: On<Remove, RigidBodyHandleComponent>,
+    handles: Query<&RigidBodyHandleComponent>,
+    mut remover: BodyRemover,
+) {
+    match handles.get(remove.entity) {
+        Ok(handle) => remover.remove(remove.entity, handle.0),
+        Err(_) => {
+            remover.owners.0.remove(&remove.entity);
+        }
+    }
+}
+
+/// Ciało encji, która zniknęła, zanim dostała uchwyty (np. despawn w tej samej klatce co `inspect`)
+pub fn remove_orphan_body(In((entity, handle)): In<(Entity, RigidBodyHandle)>, mut remover: BodyRemover) {
+    remover.remove(entity, handle);
+}
+
+/// Usuwa z mapy encje, których ciała zniknęły z rapiera (po przekazaniu ostatnich zdarzeń)
+fn prune_entity_map(
+    rigid_bodies: Res<ResRigidBodySet>,
+    mut entity_map: ResMut<PhysicsEntityMap>,
+) {
+    for entity in entity_map.stale_entities(&rigid_bodies.0) {
+        entity_map.remove_entity(entity);
+    }
+}
+
+/// Pauza (menu, brak gry, zamrożenie) i skala czasu przez Time<Virtual> - działa tak samo
+/// na FixedUpdate, timery i animacje
+fn apply_simulation_control(
+    control: Res<SimulationControl>,
+    state: Res<State<AppState>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let paused = *state.get() != AppState::Playing || control.frozen;
+    if paused && !virtual_time.is_paused() {
+        virtual_time.pause();
+    } else if !paused && virtual_time.is_paused() {
+        virtual_time.unpause();
+    }
+    if virtual_time.relative_speed() != control.time_scale {
+        virtual_time.set_relative_speed(control.time_scale);
+    }
+}
+
+/// W zamrożeniu wykonuje zlecone kroki stałe (tak jak FixedMain, z Time ustawionym na Time<Fixed>)
+fn run_single_steps(world: &mut World) {
+    let (steps, frozen) = {
+        let mut control = world.resource_mut::<SimulationControl>();
+        (std::mem::take(&mut control.pending_steps), control.frozen)
+    };
+    if steps == 0 || !frozen {
+        return;
+    }
+    for _ in 0..steps {
+        let fixed = world.resource::<Time<Fixed>>().as_generic();
+        *world.resource_mut::<Time>() = fixed;
+        world.run_schedule(FixedPreUpdate);
+        world.run_schedule(FixedUpdate);
+        world.run_schedule(FixedPostUpdate);
+    }
+    let virtual_time = world.resource::<Time<Virtual>>().as_generic();
+    *world.resource_mut::<Time>() = virtual_time;
+}
+
+/// Ustawia krok Time<Fixed> i dt rapiera na tę samą wartość
+fn apply_physics_timestep(
+    timestep: Res<PhysicsTimestep>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut integration_parameters: ResMut<ResIntegrationParameters>,
+) {
+    fixed_time.set_timestep_hz(timestep.hz);
+    integration_parameters.0.dt = (1.0 / timestep.hz) as f32;
+}
+
 fn sync_physics_to_transform(
     rigid_bodies: Res<ResRigidBodySet>,
-    mut query_single: Query<(&RigidBodyHandleComponent, &mut Transform)>,
+    mut query_single: Query<(&RigidBodyHandleComponent, &mut Transform, Option<&mut PhysicsPose>, Has<NoInterpolation>)>,
 ) {
-    for (rb_handle, mut transform) in &mut query_single {
+    for (rb_handle, mut transform, pose, no_interpolation) in &mut query_single {
         if let Some(rb) = rigid_bodies.0.get(rb_handle.0) {
             let pos = rb.position();
             let translation = pos.translation;
-            let rotation = pos.rotation.angle();;
+            let rotation = pos.rotation.angle();
+
+            // interpolowane encje dostają Transform w interpolate_transforms
+            if let Some(mut pose) = pose {
+                pose.previous = pose.current;
+                pose.previous_rotation = pose.current_rotation;
+                pose.current = Vec2::new(translation.x, translation.y);
+                pose.current_rotation = rotation;
+                if !no_interpolation {
+                    continue;
+                }
+            }
 
             transform.translation.x = translation.x;
             transform.translation.y = translation.y;
@@ -78,4 +474,200 @@ fn sync_physics_to_transform(
             transform.rotation = Quat::from_rotation_z(rotation);
         }
     }
-}
\ No newline at end of file
+}
+
+/// Rysuje ciała między dwoma ostatnimi krokami wg ułamka nadmiarowego czasu Time<Fixed>
+fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&PhysicsPose, &mut Transform), Without<NoInterpolation>>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    for (pose, mut transform) in &mut query {
+        let pos = pose.previous.lerp(pose.current, alpha);
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+        let rotation = Rot2::radians(pose.previous_rotation).slerp(Rot2::radians(pose.current_rotation), alpha);
+        transform.rotation = Quat::from_rotation_z(rotation.as_radians());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::headless::{match_frame_to_timestep, HeadlessPlugin, HeadlessPlugins};
+    use crate::systems::loader::ObjectsLoaderPlugin;
+
+    /// Wiadomości kolizji zebrane ze wszystkich kroków
+    #[derive(Resource, Default)]
+    struct Collected {
+        started: Vec<CollisionStarted>,
+        stopped: Vec<CollisionStopped>,
+    }
+
+    fn collect(
+        mut started: MessageReader<CollisionStarted>,
+        mut stopped: MessageReader<CollisionStopped>,
+        mut collected: ResMut<Collected>,
+    ) {
+        collected.started.extend(started.read().copied());
+        collected.stopped.extend(stopped.read().copied());
+    }
+
+    /// Sama fizyka bez okna: jedna klatka = jeden krok, od razu w Playing
+    fn physics_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((HeadlessPlugins.build().disable::<HeadlessPlugin>(), PhysicsPlugin, ObjectsLoaderPlugin))
+            .add_systems(Update, match_frame_to_timestep.run_if(resource_changed::<PhysicsTimestep>))
+            .init_resource::<Collected>()
+            .add_systems(FixedUpdate, collect.after(forward_collision_events));
+        app.update();
+        app.world_mut().resource_mut::<NextState<AppState>>().set(AppState::Playing);
+        app.update();
+        app
+    }
+
+    fn step(app: &mut App, frames: u32) {
+        for _ in 0..frames {
+            app.update();
+        }
+    }
+
+    fn spawn_body(app: &mut App, body: PhysicsBody, pos: Vec2, sensor: bool) -> Entity {
+        app.world_mut().spawn((
+            body,
+            BodyShape::Ball { radius: 10.0 },
+            ColliderProps { sensor, ..default() },
+            Transform::from_xyz(pos.x, pos.y, 0.0),
+            Pending,
+        )).id()
+    }
+
+    fn is_pair(a: Entity, b: Entity, first: Entity, second: Entity) -> bool {
+        (a, b) == (first, second) || (a, b) == (second, first)
+    }
+
+    #[test]
+    fn overlap_starts_and_separation_stops_collision() {
+        let mut app = physics_app();
+        let zone = spawn_body(&mut app, PhysicsBody::Fixed, Vec2::ZERO, true);
+        let character = spawn_body(&mut app, PhysicsBody::Kinematic, Vec2::new(5.0, 0.0), false);
+        step(&mut app, 4);
+
+        let collected = app.world().resource::<Collected>();
+        assert_eq!(collected.started.len(), 1);
+        let started = collected.started[0];
+        assert!(is_pair(started.a, started.b, zone, character));
+        assert!(started.sensor);
+        assert!(collected.stopped.is_empty());
+
+        let handle = app.world().get::<RigidBodyHandleComponent>(character).unwrap().0;
+        app.world_mut().resource_mut::<ResRigidBodySet>().0[handle].set_next_kinematic_translation(vector![100.0, 0.0]);
+        step(&mut app, 4);
+
+        let collected = app.world().resource::<Collected>();
+        assert_eq!(collected.started.len(), 1);
+        assert_eq!(collected.stopped.len(), 1);
+        let stopped = collected.stopped[0];
+        assert!(is_pair(stopped.a, stopped.b, zone, character));
+    }
+
+    #[test]
+    fn despawn_stops_collision() {
+        let mut app = physics_app();
+        let zone = spawn_body(&mut app, PhysicsBody::Fixed, Vec2::ZERO, true);
+        let character = spawn_body(&mut app, PhysicsBody::Kinematic, Vec2::new(5.0, 0.0), false);
+        step(&mut app, 4);
+        assert_eq!(app.world().resource::<Collected>().started.len(), 1);
+
+        app.world_mut().despawn(character);
+        step(&mut app, 4);
+
+        let collected = app.world().resource::<Collected>();
+        assert_eq!(collected.stopped.len(), 1);
+        let stopped = collected.stopped[0];
+        assert!(is_pair(stopped.a, stopped.b, zone, character));
+    }
+
+    fn physics_counts(app: &App) -> [usize; 4] {
+        let world = app.world();
+        [
+            world.resource::<ResRigidBodySet>().0.len(),
+            world.resource::<ResColliderSet>().0.len(),
+            world.resource::<ResImpulseJointSet>().0.len(),
+            world.resource::<ResMultibodyJointSet>().0.iter().count(),
+        ]
+    }
+
+    #[test]
+    fn despawn_removes_bodies_colliders_and_joints() {
+        let mut app = physics_app();
+        let first = spawn_body(&mut app, PhysicsBody::Dynamic, Vec2::ZERO, false);
+        let second = spawn_body(&mut app, PhysicsBody::Dynamic, Vec2::new(50.0, 0.0), false);
+        step(&mut app, 2);
+
+        let body_of = |entity| app.world().get::<RigidBodyHandleComponent>(entity).unwrap().0;
+        let (first_body, second_body) = (body_of(first), body_of(second));
+        // dodatkowy collider na pierwszym ciele - też musi zniknąć
+        app.world_mut().resource_scope(|world, mut colliders: Mut<ResColliderSet>| {
+            let mut rigid_bodies = world.resource_mut::<ResRigidBodySet>();
+            colliders.0.insert_with_parent(ColliderBuilder::ball(5.0).build(), first_body, &mut rigid_bodies.0);
+        });
+        app.world_mut().resource_mut::<ResImpulseJointSet>().0.insert(first_body, second_body, FixedJointBuilder::new(), true);
+        step(&mut app, 2);
+        assert_eq!(physics_counts(&app), [2, 3, 1, 0]);
+
+        app.world_mut().despawn(first);
+        app.world_mut().despawn(second);
+        step(&mut app, 2);
+
+        assert_eq!(physics_counts(&app), [0, 0, 0, 0]);
+        let entity_map = app.world().resource::<PhysicsEntityMap>();
+        assert!(entity_map.body_of(first).is_none() && entity_map.body_of(second).is_none());
+    }
+
+    #[test]
+    fn despawning_parent_removes_child_bodies() {
+        let mut app = physics_app();
+        let child = spawn_body(&mut app, PhysicsBody::Kinematic, Vec2::ZERO, true);
+        let parent = app.world_mut().spawn(Transform::default()).add_child(child).id();
+        step(&mut app, 2);
+        assert_eq!(physics_counts(&app), [1, 1, 0, 0]);
+
+        app.world_mut().despawn(parent);
+        step(&mut app, 2);
+        assert_eq!(physics_counts(&app), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn despawn_in_the_inspect_frame_leaves_no_body() {
+        let mut app = physics_app();
+        let projectile = spawn_body(&mut app, PhysicsBody::Dynamic, Vec2::ZERO, false);
+        app.world_mut().entity_mut(projectile).insert(ProjectileOwner(Entity::PLACEHOLDER));
+
+        // despawn jak przy wczytaniu gry: po inspect, ale przed wykonaniem jego komend
+        let mut inspect = IntoSystem::into_system(crate::systems::loader::inspect);
+        inspect.initialize(app.world_mut());
+        inspect.run((), app.world_mut()).unwrap();
+        app.world_mut().despawn(projectile);
+        inspect.apply_deferred(app.world_mut());
+
+        step(&mut app, 2);
+        assert_eq!(physics_counts(&app), [0, 0, 0, 0]);
+        assert!(app.world().resource::<ProjectileOwners>().0.is_empty());
+    }
+
+    #[test]
+    fn projectile_does_not_collide_with_owner() {
+        let mut app = physics_app();
+        let owner = spawn_body(&mut app, PhysicsBody::Dynamic, Vec2::ZERO, false);
+        let other = spawn_body(&mut app, PhysicsBody::Dynamic, Vec2::new(30.0, 0.0), false);
+        let projectile = spawn_body(&mut app, PhysicsBody::Dynamic, Vec2::new(15.0, 0.0), false);
+        app.world_mut().entity_mut(projectile).insert(ProjectileOwner(owner));
+        step(&mut app, 4);
+
+        let collected = app.world().resource::<Collected>();
+        assert!(collected.started.iter().all(|ev| !is_pair(ev.a, ev.b, owner, projectile)));
+        assert!(collected.started.iter().any(|ev| is_pair(ev.a, ev.b, other, projectile)));
+    }
+}
+
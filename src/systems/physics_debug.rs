@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use bevy::camera::visibility::RenderLayers;
+use bevy_firefly::prelude::*;
+use bevy_firefly::occluders::Occluder2dShape;
+use rapier2d::prelude::*;
+use crate::resourses::physics_resources::*;
+use crate::systems::actions::{Action, ActionState};
+
+#[derive(Default)]
+pub struct PhysicsDebugPlugin {
+    /// skróty sterujące symulacją (--debug) - nie dla zwykłego gracza
+    pub hotkeys: bool,
+}
+
+const MIN_TIME_SCALE: f32 = 0.125;
+const MAX_TIME_SCALE: f32 = 4.0;
+/// długość strzałki prędkości na 1 px/s
+const VELOCITY_SCALE: f32 = 0.25;
+
+/// Czy rysować nakładkę debugową fizyki
+#[derive(Resource, Default)]
+pub struct PhysicsDebug(pub bool);
+
+impl Plugin for PhysicsDebugPlugin {
+    fn build(&self, app: &mut App) {
+        let hotkeys = self.hotkeys;
+        app.init_resource::<PhysicsDebug>()
+            .add_systems(Startup, setup_debug_gizmos)
+            .add_systems(Update, (
+                (toggle_physics_debug, simulation_hotkeys.run_if(move || hotkeys)),
+                (draw_colliders, draw_contacts, draw_velocities, draw_occluders)
+                    .run_if(|debug: Res<PhysicsDebug>| debug.0),
+            ).chain().run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))));
+    }
+}
+
+/// Gizmo na warstwie efektów - rysowane nad oświetleniem, żeby było widać je w nocy
+fn setup_debug_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
+    config.render_layers = RenderLayers::from_layers(CAMERA_LAYER_EFFECT);
+}
+
+fn toggle_physics_debug(
+    actions: Res<ActionState>,
+    mut debug: ResMut<PhysicsDebug>,
+) {
+    if actions.just_pressed(Action::DebugOverlay) {
+        debug.0 = !debug.0;
+    }
+}
+
+/// Zamrożenie (F6), jeden krok fizyki (F7), zwolnienie/przyspieszenie ([ ]), normalna prędkość (\)
+fn simulation_hotkeys(
+    actions: Res<ActionState>,
+    mut control: ResMut<SimulationControl>,
+) {
+    if actions.just_pressed(Action::PhysicsFreeze) {
+        control.frozen = !control.frozen;
+    }
+    if actions.just_pressed(Action::PhysicsStep) {
+        control.frozen = true;
+        control.pending_steps += 1;
+    }
+    if actions.just_pressed(Action::PhysicsSlower) {
+        control.time_scale = (control.time_scale / 2.0).max(MIN_TIME_SCALE);
+    }
+    if actions.just_pressed(Action::PhysicsFaster) {
+        control.time_scale = (control.time_scale * 2.0).min(MAX_TIME_SCALE);
+    }
+    if actions.just_pressed(Action::PhysicsResetSpeed) {
+        control.time_scale = 1.0;
+    }
+}
+
+/// Kolor collidera: sensor żółty, wyłączony szary, reszta wg typu ciała (uśpione przygaszone)
+fn collider_color(collider: &Collider, body: Option<&RigidBody>) -> Color {
+    if !collider.is_enabled() {
+        return Color::srgba(0.5, 0.5, 0.5, 0.4);
+    }
+    if collider.is_sensor() {
+        return Color::srgb(1.0, 0.9, 0.2);
+    }
+    let Some(body) = body else {
+        return Color::srgb(1.0, 1.0, 1.0);
+    };
+    let color = match body.body_type() {
+        RigidBodyType::Dynamic => Color::srgb(0.2, 1.0, 0.3),
+        RigidBodyType::Fixed => Color::srgb(0.6, 0.6, 0.9),
+        RigidBodyType::KinematicPositionBased | RigidBodyType::KinematicVelocityBased => Color::srgb(0.3, 0.8, 1.0),
+    };
+    if body.is_sleeping() {
+        color.with_alpha(0.35)
+    } else {
+        color
+    }
+}
+
+fn draw_colliders(
+    rigid_bodies: Res<ResRigidBodySet>,
+    colliders: Res<ResColliderSet>,
+    mut gizmos: Gizmos,
+) {
+    for (_, collider) in colliders.0.iter() {
+        let body = collider.parent().and_then(|handle| rigid_bodies.0.get(handle));
+        let color = collider_color(collider, body);
+        let pos = collider.position();
+        let isometry = Isometry2d::new(
+            Vec2::new(pos.translation.x, pos.translation.y),
+            Rot2::radians(pos.rotation.angle()),
+        );
+        let to_world = |p: &Point<f32>| {
+            let p = pos * p;
+            Vec2::new(p.x, p.y)
+        };
+
+        match collider.shape().as_typed_shape() {
+            TypedShape::Cuboid(cuboid) => {
+                let size = Vec2::new(cuboid.half_extents.x, cuboid.half_extents.y) * 2.0;
+                gizmos.rect_2d(isometry, size, color);
+            }
+            TypedShape::Ball(ball) => {
+                gizmos.circle_2d(isometry, ball.radius, color);
+            }
+            TypedShape::Capsule(capsule) => {
+                let a = to_world(&capsule.segment.a);
+                let b = to_world(&capsule.segment.b);
+                let side = (b - a).perp().normalize_or_zero() * capsule.radius;
+                gizmos.circle_2d(a, capsule.radius, color);
+                gizmos.circle_2d(b, capsule.radius, color);
+                gizmos.line_2d(a + side, b + side, color);
+                gizmos.line_2d(a - side, b - side, color);
+            }
+            TypedShape::TriMesh(trimesh) => {
+                for triangle in trimesh.triangles() {
+                    let (a, b, c) = (to_world(&triangle.a), to_world(&triangle.b), to_world(&triangle.c));
+                    gizmos.linestrip_2d([a, b, c, a], color);
+                }
+            }
+            _ => {
+                // pozostałe kształty - tylko AABB
+                let aabb = collider.compute_aabb();
+                let min = Vec2::new(aabb.mins.x, aabb.mins.y);
+                let max = Vec2::new(aabb.maxs.x, aabb.maxs.y);
+                gizmos.rect_2d((min + max) / 2.0, max - min, color);
+            }
+        }
+    }
+}
+
+/// Punkty kontaktu z ostatniego kroku
+fn draw_contacts(
+    narrow_phase: Res<ResNarrowPhase>,
+    mut gizmos: Gizmos,
+) {
+    for pair in narrow_phase.0.contact_pairs() {
+        if !pair.has_any_active_contact {
+            continue;
+        }
+        for manifold in &pair.manifolds {
+            for contact in &manifold.data.solver_contacts {
+                gizmos.circle_2d(Vec2::new(contact.point.x, contact.point.y), 3.0, Color::srgb(1.0, 0.2, 0.2));
+            }
+        }
+    }
+}
+
+fn draw_velocities(
+    rigid_bodies: Res<ResRigidBodySet>,
+    mut gizmos: Gizmos,
+) {
+    for (_, body) in rigid_bodies.0.iter() {
+        let velocity = Vec2::new(body.linvel().x, body.linvel().y);
+        if body.is_fixed() || velocity.length_squared() < 1.0 {
+            continue;
+        }
+        let start = Vec2::new(body.translation().x, body.translation().y);
+        gizmos.arrow_2d(start, start + velocity * VELOCITY_SCALE, Color::srgb(1.0, 0.5, 0.0));
+    }
+}
+
+/// Occludery światła (także szczeliny między ścianami z terrain::add_gap_occluders_for_tile)
+fn draw_occluders(
+    occluders: Query<(&Occluder2d, &GlobalTransform)>,
+    mut gizmos: Gizmos,
+) {
+    let color = Color::srgb(0.9, 0.2, 0.9);
+    for (occluder, transform) in &occluders {
+        let center = transform.translation().truncate() + occluder.offset.truncate();
+        let isometry = Isometry2d::new(center, Rot2::radians(transform.rotation().to_euler(EulerRot::XYZ).2));
+        match occluder.shape() {
+            Occluder2dShape::RoundRectangle { width, height, radius } => {
+                gizmos.rect_2d(isometry, Vec2::new(*width, *height) + 2.0 * *radius, color);
+            }
+            Occluder2dShape::Polygon { vertices } => {
+                let first = vertices.first().copied();
+                gizmos.linestrip_2d(
+                    vertices.iter().chain(first.as_ref()).map(|v| isometry * *v),
+                    color,
+                );
+            }
+            Occluder2dShape::Polyline { vertices } => {
+                gizmos.linestrip_2d(vertices.iter().map(|v| isometry * *v), color);
+            }
+        }
+    }
+}
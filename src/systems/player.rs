@@ -1,11 +1,11 @@
 use bevy::prelude::*;
 use crate::resourses::physics_resources::*;
+use crate::systems::terrain::TerrainMap;
+use crate::systems::actions::{Action, ActionState};
 
-use rapier2d::prelude::*;
 use rapier2d::na::Point2;
 
 pub struct PlayerPlugin;
-use bevy::window::{PrimaryWindow, Window};
 use bevy_2d_screen_space_lightmaps::lightmap_plugin::lightmap_plugin::*;
 use bevy::camera::visibility::RenderLayers;
 use bevy::camera::{ImageRenderTarget, RenderTarget};
@@ -15,20 +15,27 @@ use bevy_firefly::prelude::*;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         //app.add_systems(Startup, init);
-        app.add_systems(Update, (update, animate_sprite, try_heal).run_if(|status: Res<GameStatus>, status2: Res<ResumeStatus>| status.0 && !status2.0) );
+        app.add_systems(Update, (update, animate_sprite, try_heal, apply_environment).run_if(in_state(AppState::Playing)) );
+    }
+}
+
+/// Część gracza potrzebna tylko do wyświetlania
+pub struct PlayerRenderPlugin;
+
+impl Plugin for PlayerRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, attach_player_cameras);
     }
 }
 
 pub fn init(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
     asset_server: &Res<AssetServer>,
     texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    images: &Res<Assets<Image>>,
     config: &Res<ItemConfig>,
     atlas_handles: &Res<AtlasHandles>,
-) {
+) -> Entity {
     let texture = asset_server.load("textures/player_combined.png");
     let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 2, 5, None, None);
     let texture_atlas_layout = texture_atlas_layouts.add(layout);
@@ -54,6 +61,11 @@ pub fn init(
         PlayerData::new(config),
         Mesh2d(meshes.add(Rectangle::new(50.0, 42.5))),
         Pending,
+        PhysicsBody::Kinematic,
+        CharacterController::default(),
+        KnockbackProps { mass: 1.5, resistance: 0.2 },
+        BodyShape::Cuboid { half_extents: Vec2::new(25.0, 21.25) },
+        CollisionLayer::Player,
         Transform::from_xyz(
             0.0,
             0.0,
@@ -61,29 +73,6 @@ pub fn init(
         ),
         RenderLayers::from_layers(CAMERA_LAYER_ENTITY),
         children![(
-            Camera2d,
-            Camera {
-                order: 0,
-                ..default()
-            },
-            RenderLayers::from_layers(CAMERA_LAYER_ENTITY),
-            FireflyConfig {
-                //ambient_color: Color::srgba(0.0, 0.0, 0.0, 1.0),
-                ambient_brightness: 0.0025,
-                z_sorting: true,
-                softness: Some(0.5),
-                ..default()
-            },
-            PlayerCamera
-        ),(
-            Camera2d,
-            Camera {
-                order: 1,
-                ..default()
-            },
-            RenderLayers::from_layers(CAMERA_LAYER_EFFECT),
-        ),
-        (
             Sprite::from_atlas_image(
                 texture,
                 TextureAtlas {
@@ -100,25 +89,54 @@ pub fn init(
             AttackStatus(false),
         ),(
             Transform::from_xyz(0.0, 15.0, 0.0),
-            PointLight2d {
+            LightSource {
                 range: 750.0,
                 intensity: 0.125,
                 color: Color::WHITE,
-                ..default()
             },
             YSort { z: 0.0 },
         )]
-    ));
+    )).id()
+}
+
+/// Kamery gracza (świat z oświetleniem + efekty) - tylko z renderowaniem
+fn attach_player_cameras(mut commands: Commands, players: Query<Entity, Added<Player>>) {
+    for player in &players {
+        commands.entity(player).with_children(|parent| {
+            parent.spawn((
+                Camera2d,
+                Camera {
+                    order: 0,
+                    ..default()
+                },
+                RenderLayers::from_layers(CAMERA_LAYER_ENTITY),
+                FireflyConfig {
+                    //ambient_color: Color::srgba(0.0, 0.0, 0.0, 1.0),
+                    ambient_brightness: 0.0025,
+                    z_sorting: true,
+                    softness: Some(0.5),
+                    ..default()
+                },
+                PlayerCamera
+            ));
+            parent.spawn((
+                Camera2d,
+                Camera {
+                    order: 1,
+                    ..default()
+                },
+                RenderLayers::from_layers(CAMERA_LAYER_EFFECT),
+            ));
+        });
+    }
 }
 
 fn animate_sprite(
     time: Res<Time>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     mut query: Query<(&mut AnimationIndices, &mut AnimationTimer, &mut Sprite, &mut Transform, &mut AttackStatus), With<PlayerSprite>>,
     asset_server: Res<AssetServer>,
     atlas_handles: Res<AtlasHandles>,
-    mouse: Res<ButtonInput<MouseButton>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
 ) {
     for (mut indices, mut timer, mut sprite, mut transform, mut atack) in &mut query {
         timer.tick(time.delta());
@@ -126,8 +144,9 @@ fn animate_sprite(
         // Sprawdzenie czy gracz naciska klawisze ruchu
         let mut moving = false;
         let mut direction: f32 = 1.0; // 1 = prawo, -1 = lewo
+        let input = actions.movement();
 
-        if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::KeyS) {
+        if input.y != 0.0 {
             if transform.scale.x < 0.0 {
                 direction = -1.0;
             } else {
@@ -135,11 +154,11 @@ fn animate_sprite(
             }
             moving = true;
         }
-        if keyboard_input.pressed(KeyCode::KeyA) {
+        if input.x < 0.0 {
             moving = true;
             direction = -1.0;
         }
-        if keyboard_input.pressed(KeyCode::KeyD) {
+        if input.x > 0.0 {
             moving = true;
             direction = 1.0;
         }
@@ -149,14 +168,9 @@ fn animate_sprite(
 
         if moving || atack.0 {
             if atack.0 {
-                let window = match windows.single() {
-                    Ok(w) => w,
-                    Err(_) => return, // brak okna głównego
-                };
-
-                if let Some(cursor_pos) = window.cursor_position() {
-                    let screen_center_x = window.width() / 2.0;
-                    let direction = if cursor_pos.x > screen_center_x { 1.0 } else { -1.0 };
+                // bez kursora (np. bez okna) gracz zostaje zwrócony jak był
+                if let Some(aim) = actions.aim() {
+                    let direction = if aim.x > 0.0 { 1.0 } else { -1.0 };
                     transform.scale.x = direction * transform.scale.x.abs();
                 }
             } else {
@@ -197,31 +211,22 @@ fn animate_sprite(
 
 fn update(
     time: Res<Time>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&RigidBodyHandleComponent, &mut Transform, &mut PlayerData), (With<Player>, Without<Pending>)>,
-    mut rigid_bodies: ResMut<ResRigidBodySet>,
+    actions: Res<ActionState>,
+    mut query: Query<(&mut CharacterMovement, Has<Stunned>, &mut Transform, &mut PlayerData), (With<Player>, Without<Pending>)>,
     mut sprite_camera: Query<&mut Transform, (With<SpriteCamera>, Without<Pending>, Without<Player>)>,
     mut light_camera: Query<&mut Transform, (With<LightCamera>, Without<Pending>, Without<Player>, Without<SpriteCamera>)>,
+    terrain_map: Res<TerrainMap>,
 ) {
-    let Ok((handle, mut transform, mut player_data)) = query.single_mut() else {
+    let Ok((mut movement, stunned, mut transform, mut player_data)) = query.single_mut() else {
         return;
     };
 
-    if !rigid_bodies.0.get_mut(handle.0).is_some() {
-        return;
-    }
-
-    let mut rigidbody = rigid_bodies.0.get_mut(handle.0).unwrap();
-
-    let mut dir = Vec2::ZERO;
-    if keyboard_input.pressed(KeyCode::KeyW) { dir.y += 1.0; }
-    if keyboard_input.pressed(KeyCode::KeyS) { dir.y -= 1.0; }
-    if keyboard_input.pressed(KeyCode::KeyA) { dir.x -= 1.0; }
-    if keyboard_input.pressed(KeyCode::KeyD) { dir.x += 1.0; }
+    // gałka pada daje ruch wolniejszy niż pełne wychylenie
+    let dir = actions.movement();
 
     let mut speed = 200.0;
 
-    if keyboard_input.pressed(KeyCode::ShiftLeft) && dir != Vec2::ZERO { 
+    if actions.pressed(Action::Sprint) && dir != Vec2::ZERO { 
         player_data.run(1.25, &time);
         speed = 350.0; 
     } else {
@@ -232,80 +237,38 @@ fn update(
         speed *= player_data.fatigue();
     }
 
-    let velocity = if dir.length_squared() > 0.0 {
-        dir.normalize() * speed
-    } else {
+    if let Some(kind) = terrain_map.hazard_at(transform.translation.xy()) {
+        speed *= kind.speed_multiplier();
+    }
+
+    // ogłuszony gracz nie steruje - porusza nim tylko odrzut
+    let velocity = if stunned {
         Vec2::ZERO
+    } else {
+        dir * speed
     };
 
-    rigidbody.set_linvel(vector![velocity.x, velocity.y], true);
+    movement.velocity = velocity;
     //transform.translation.z = -(((WORLD_SIZE as f32*TILE_SIZE)/2.0)/64.0 + rigidbody.translation().y.round()/64.0) + 64.0;
 }
 
 fn try_heal(
-    mut rigid_bodies: ResMut<ResRigidBodySet>,
-    mut colliders: ResMut<ResColliderSet>,
-    mut island_manager: ResMut<ResIslandManager>,
     mut commands: Commands,
     time: Res<Time>,
-    mut player_query: Query<(Entity, &Transform, &mut PlayerData, &RigidBodyHandleComponent), (With<Player>, Without<Pending>)>,
-    mut player_ui_query: Query<Entity, With<PlayerUIs>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    images: Res<Assets<Image>>,
-    mut game_status: ResMut<GameStatus>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut resume_status: ResMut<ResumeStatus>,
+    mut player_query: Query<(Entity, &Transform, &mut PlayerData), (With<Player>, Without<Pending>, With<RigidBodyHandleComponent>)>,
+    mut next_state: ResMut<NextState<AppState>>,
 ) {
-    let (entity, transform, mut player_data, handle) = if let Ok((e, t, mut d, rb_handle)) = player_query.single_mut() {
-        (e, t, d, rb_handle)
+    let (entity, transform, mut player_data) = if let Ok((e, t, mut d)) = player_query.single_mut() {
+        (e, t, d)
     } else {
         return;
     };
 
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        resume_status.0 = true;
-        for ui_entity in player_ui_query {
-            commands.entity(ui_entity).despawn();
-        }
-        crate::systems::menu_ui::setup_ui(&mut commands, &asset_server);
-        return;
-    }
-
     if player_data.can_heal.just_finished() && player_data.health < player_data.max_health && player_data.health > 0.0 {
         player_data.heal(1.0, &time);
     } else if player_data.health == 0.0 {
-        game_status.0 = false;
-        let mut colliders_clone = Vec::new();
-        if let Some(rb) = rigid_bodies.0.get(handle.0) {
-            for collider_handle in rb.colliders() {
-                colliders_clone.push(collider_handle.clone());
-            }
-        }
-
-        for collider_handle in colliders_clone {
-            colliders.0.remove(collider_handle, &mut island_manager.0, &mut rigid_bodies.0, true);
-        }
-        rigid_bodies.0.remove(
-            handle.0,
-            &mut island_manager.0,
-            &mut colliders.0,
-            &mut ImpulseJointSet::new(),
-            &mut MultibodyJointSet::new(),
-            true, // usuwa powiązane collidery
-        );
         commands.entity(entity).despawn();
-        for ui_entity in player_ui_query {
-            commands.entity(ui_entity).despawn();
-        }
-        crate::systems::menu_ui::setup_ui(&mut commands, &asset_server);
-        commands.spawn((
-            Camera2d,
-            //SpriteCamera, AnyNormalCamera,
-            MenuCamera
-        ));
+        next_state.set(AppState::GameOver);
         //commands.spawn((Camera2d, Transform {translation: transform.translation, ..default()}));
         /*let texture = asset_server.load("textures/player_sprite.png");
         let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 2, 2, None, None);
@@ -339,4 +302,50 @@ fn try_heal(
         return;
     }
     player_data.can_heal.tick(time.delta());
-}
\ No newline at end of file
+}
+
+/// Efekty środowiskowe biomu, w którym stoi gracz (zimno, skażenie itp.)
+fn apply_environment(
+    time: Res<Time>,
+    terrain_map: Res<TerrainMap>,
+    biome_config: Res<BiomeConfig>,
+    mut player_query: Query<(&Transform, &mut PlayerData), (With<Player>, Without<Pending>)>,
+    mut indicator_query: Query<(&mut Text, &mut Visibility), With<EnvironmentIndicator>>,
+) {
+    let Ok((transform, mut player_data)) = player_query.single_mut() else {
+        return;
+    };
+
+    let effect = terrain_map
+        .biome_at(transform.translation.xy())
+        .and_then(|biome| biome_config.biomes.get(&biome))
+        .filter(|effect| match &effect.protective_item {
+            Some(item_id) => !player_data.inventory.contains(item_id),
+            None => true,
+        })
+        .filter(|effect| effect.stamina_drain > 0.0 || effect.damage_per_second > 0.0);
+
+    if let Ok((mut text, mut visibility)) = indicator_query.single_mut() {
+        match effect {
+            Some(effect) => {
+                if text.0 != effect.name {
+                    text.0 = effect.name.clone();
+                }
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    let Some(effect) = effect else {
+        return;
+    };
+    let dt = time.delta_secs();
+    if effect.stamina_drain > 0.0 {
+        player_data.satamina = (player_data.satamina - effect.stamina_drain * dt).clamp(0.0, player_data.max_satamina);
+    }
+    if effect.damage_per_second > 0.0 {
+        player_data.damage(effect.damage_per_second * dt);
+        player_data.can_heal.reset();
+    }
+}
@@ -1,15 +1,28 @@
 use bevy::prelude::*;
 use bevy::color::palettes::css::*;
 use crate::resourses::physics_resources::*;
+use crate::systems::settings::{Language, Settings};
+use crate::systems::actions::{Action, ActionState, HOTBAR_SLOTS};
 pub struct HudPlugin;
+/// Wybór slotu i użycie przedmiotu - logika bez HUD
+pub struct InventoryPlugin;
 
 const SCALE: f32 = 1.5;
+const SAVING_INDICATOR_SECONDS: f32 = 1.5;
 
 impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::Playing), spawn_hud)
+            .add_systems(Update, (update_health_bar, update_satamina_bar, update_score_text, update_saving_indicator, update_inventory_ui).run_if(in_state(AppState::Playing)));
+    }
+}
+
+impl Plugin for InventoryPlugin {
     fn build(&self, app: &mut App) {
         app
             .insert_resource(InventoryState::default())
-            .add_systems(Update, (update_health_bar, update_satamina_bar, handle_inventory_input, update_inventory_ui, ui_use_item).run_if(|status: Res<GameStatus>, status2: Res<ResumeStatus>| status.0 && !status2.0));
+            .add_systems(Update, (handle_inventory_input, ui_use_item).run_if(in_state(AppState::Playing)));
     }
 }
 
@@ -22,11 +35,41 @@ impl Default for InventoryState {
     }
 }
 
+/// HUD istnieje tylko w Playing (pauza i koniec gry go chowają)
+fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>, settings: Res<Settings>) {
+    spawn_health_bar(&mut commands, &asset_server);
+    spawn_inventory_bar(&mut commands, &asset_server);
+    spawn_saving_indicator(&mut commands, &asset_server, settings.language);
+}
+
+fn spawn_saving_indicator(commands: &mut Commands, asset_server: &Res<AssetServer>, language: Language) {
+    commands.spawn((
+        PlayerUIs,
+        DespawnOnExit(AppState::Playing),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0 * SCALE),
+            right: Val::Px(10.0 * SCALE),
+            ..default()
+        },
+        Text::new(language.text("Saving...")),
+        TextFont {
+            font: asset_server.load("fonts/Cantarell-Bold.ttf"),
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Visibility::Hidden,
+        SavingIndicator(Timer::from_seconds(SAVING_INDICATOR_SECONDS, TimerMode::Once)),
+    ));
+}
+
 pub fn spawn_health_bar(commands: &mut Commands, asset_server: &Res<AssetServer>) {
     // Kontener paska zdrowia
     commands
         .spawn((
             PlayerUIs,
+            DespawnOnExit(AppState::Playing),
             Node {
                 position_type: PositionType::Absolute,
                 top: Val::Px(10.0 * SCALE),
@@ -53,6 +96,7 @@ pub fn spawn_health_bar(commands: &mut Commands, asset_server: &Res<AssetServer>
     commands
         .spawn((
             PlayerUIs,
+            DespawnOnExit(AppState::Playing),
             Node {
                 position_type: PositionType::Absolute,
                 top: Val::Px(40.0 * SCALE),
@@ -80,6 +124,7 @@ pub fn spawn_health_bar(commands: &mut Commands, asset_server: &Res<AssetServer>
     commands
         .spawn((
             PlayerUIs,
+            DespawnOnExit(AppState::Playing),
             Node {
                 position_type: PositionType::Absolute,
                 top: Val::Px(60.0 * SCALE),
@@ -104,7 +149,33 @@ pub fn spawn_health_bar(commands: &mut Commands, asset_server: &Res<AssetServer>
                     ..default()
                 },
                 TextColor(Color::WHITE),
-                PointText(0),
+                PointText,
+            ));
+        });
+
+    // Wskaźnik aktywnego efektu środowiskowego (zimno, skażenie itp.)
+    commands
+        .spawn((
+            PlayerUIs,
+            DespawnOnExit(AppState::Playing),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(85.0 * SCALE),
+                left: Val::Px(10.0 * SCALE),
+                ..default()
+            },
+        ))
+        .with_children(|builder| {
+            builder.spawn((
+                Text::new(""),
+                TextFont {
+                    font: asset_server.load("fonts/Cantarell-Bold.ttf"),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.85, 1.0)),
+                Visibility::Hidden,
+                EnvironmentIndicator,
             ));
         });
 }
@@ -114,6 +185,7 @@ pub fn spawn_inventory_bar(commands: &mut Commands, asset_server: &Res<AssetServ
     commands
         .spawn((
             PlayerUIs,
+            DespawnOnExit(AppState::Playing),
             Node {
                 position_type: PositionType::Absolute,
                 bottom: Val::Px(20.0),
@@ -202,6 +274,34 @@ fn update_health_bar(
     }
 }
 
+/// Tekst punktów - także po ponownym utworzeniu HUD (np. po pauzie)
+fn update_score_text(
+    score: Res<Score>,
+    mut query: Query<(&mut Text, Ref<PointText>)>,
+) {
+    for (mut text, point_text) in &mut query {
+        if score.is_changed() || point_text.is_added() {
+            text.0 = format!("Points: {}", score.0);
+        }
+    }
+}
+
+fn update_saving_indicator(
+    time: Res<Time>,
+    mut saved: MessageReader<GameSaved>,
+    mut query: Query<(&mut SavingIndicator, &mut Visibility)>,
+) {
+    let just_saved = saved.read().count() > 0;
+    for (mut indicator, mut visibility) in &mut query {
+        if just_saved {
+            indicator.0.reset();
+            *visibility = Visibility::Visible;
+        } else if indicator.0.tick(time.delta()).just_finished() {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
 fn update_satamina_bar(
     mut player_query: Query<&PlayerData, (With<Player>, Without<Pending>)>,
     mut query: Query<&mut Node, With<SataminaBar>>,
@@ -219,7 +319,7 @@ fn update_satamina_bar(
 }
 
 fn handle_inventory_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     mut state: ResMut<InventoryState>,
 ) {
     /*// Otwieranie/zamykanie ekwipunku klawiszem I
@@ -227,22 +327,13 @@ fn handle_inventory_input(
         state.open = !state.open;
     }*/
 
+    if actions.just_pressed(Action::NextSlot) && state.slots > 0 {
+        state.selected = (state.selected + 1) % state.slots;
+    }
+
     // Zmiana slotu klawiszami 1-0
-    for i in 0..state.slots {
-        let key = match i {
-            0 => KeyCode::Digit1,
-            1 => KeyCode::Digit2,
-            2 => KeyCode::Digit3,
-            3 => KeyCode::Digit4,
-            4 => KeyCode::Digit5,
-            5 => KeyCode::Digit6,
-            6 => KeyCode::Digit7,
-            7 => KeyCode::Digit8,
-            8 => KeyCode::Digit9,
-            9 => KeyCode::Digit0,
-            _ => continue,
-        };
-        if keyboard.just_pressed(key) {
+    for i in 0..state.slots.min(HOTBAR_SLOTS as usize) {
+        if actions.just_pressed(Action::Hotbar(i as u8)) {
             state.selected = i;
         }
     }
@@ -295,24 +386,24 @@ fn update_inventory_ui(
 fn ui_use_item(
     mut ev_consume: ResMut<Messages<ConsumeEvent>>,
     mut ev_func: ResMut<Messages<FunctionalEvent>>,
-    //keyboard: Res<ButtonInput<KeyCode>>,
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: Res<ActionState>,
     state: Res<InventoryState>,
     query: Query<&PlayerData, With<Player>>,
 ) {
-    if mouse.just_pressed(MouseButton::Left) {
+    let (attack, use_item) = (actions.just_pressed(Action::Attack), actions.just_pressed(Action::UseItem));
+    if attack || use_item {
         let Ok(player_data) = query.single() else {
             return;
         };
         if let Some(item) = player_data.inventory.get_item(state.selected as u32) {
             match item.item_type.as_str() {
-                "food" => {
+                "food" if use_item => {
                     ev_consume.write(ConsumeEvent {
                         slot: state.selected as u32,
                         item_id: item.id.clone(),
                     });
                 }
-                "weapon" => {
+                "weapon" if attack => {
                     ev_func.write(FunctionalEvent {
                         slot: state.selected as u32,
                         item_id: item.id.clone(),
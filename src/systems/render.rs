@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy::app::PluginGroupBuilder;
+use bevy_firefly::prelude::*;
+use crate::resourses::physics_resources::*;
+use crate::systems::controls_ui::ControlsPlugin;
+use crate::systems::menu_ui::MenuPlugin;
+use crate::systems::monster::MonsterRenderPlugin;
+use crate::systems::options_ui::OptionsPlugin;
+use crate::systems::physics_debug::PhysicsDebugPlugin;
+use crate::systems::player::PlayerRenderPlugin;
+use crate::systems::player_game_ui::HudPlugin;
+use crate::systems::save_slots_ui::SaveSlotsPlugin;
+
+/// Wszystko, co potrzebuje okna i GPU: oświetlenie, kamery, menu, HUD, debug fizyki.
+/// Logika gry działa bez tej grupy (tryb headless).
+pub struct RenderPlugins;
+
+impl PluginGroup for RenderPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(FireflyPlugin)
+            .add(LightsPlugin)
+            .add(DaylightPlugin)
+            .add(PlayerRenderPlugin)
+            .add(MonsterRenderPlugin)
+            .add(MenuPlugin)
+            .add(HudPlugin)
+            .add(SaveSlotsPlugin)
+            .add(OptionsPlugin)
+            .add(ControlsPlugin)
+            .add(PhysicsDebugPlugin::default())
+    }
+}
+
+/// Światła Firefly dla źródeł światła z logiki
+pub struct LightsPlugin;
+
+impl Plugin for LightsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, attach_lights);
+    }
+}
+
+/// Jasność otoczenia kamery gracza wg pory dnia z GameClock
+pub struct DaylightPlugin;
+
+const NIGHT_BRIGHTNESS: f32 = 0.0025;
+const DAY_BRIGHTNESS: f32 = 0.06;
+
+impl Plugin for DaylightPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_daylight.run_if(in_state(AppState::Playing)));
+    }
+}
+
+fn apply_daylight(
+    clock: Res<GameClock>,
+    mut camera_query: Query<&mut FireflyConfig, With<PlayerCamera>>,
+) {
+    for mut config in &mut camera_query {
+        config.ambient_brightness = NIGHT_BRIGHTNESS + (DAY_BRIGHTNESS - NIGHT_BRIGHTNESS) * clock.daylight();
+    }
+}
+
+fn attach_lights(mut commands: Commands, lights: Query<(Entity, &LightSource), Added<LightSource>>) {
+    for (entity, light) in &lights {
+        commands.entity(entity).insert(PointLight2d {
+            range: light.range,
+            intensity: light.intensity,
+            color: light.color,
+            ..default()
+        });
+    }
+}
@@ -0,0 +1,703 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::resourses::physics_resources::*;
+use crate::systems::monster::spawn_monster;
+use crate::systems::autosave::Autosave;
+use crate::systems::structures::{Discoveries, StructureMap};
+use crate::systems::terrain::TerrainMap;
+use crate::systems::tile_sim::TileSimClock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub struct SavePlugin;
+
+/// Aktualna wersja formatu zapisu
+pub const SAVE_VERSION: u32 = 2;
+/// Slot używany, gdy gra nie została jeszcze zapisana w żadnym slocie
+pub const DEFAULT_SLOT: &str = "save";
+
+/// Migracje zapisów: MIGRATIONS[i] przenosi zapis z wersji i + 1 do i + 2.
+/// Przy zmianie formatu podbijamy SAVE_VERSION i dopisujemy tu funkcję
+const MIGRATIONS: &[fn(&mut Value)] = &[migrate_v1_to_v2];
+
+/// v2: czas gry zapisywany w slocie
+fn migrate_v1_to_v2(value: &mut Value) {
+    value["playtime"] = Value::from(0.0);
+}
+
+const GAME_DIR: &str = "mgla_2d_game";
+const META_SUFFIX: &str = ".meta.json";
+const BACKUP_DIR: &str = "backups";
+/// ile poprzednich wersji slotu trzymamy w katalogu kopii
+const MAX_BACKUPS: usize = 3;
+const PLAYER_Z: f32 = -32.0;
+
+/// Katalog ze slotami zapisu
+#[derive(Resource)]
+pub struct SaveDir(pub PathBuf);
+
+impl Default for SaveDir {
+    fn default() -> Self {
+        Self(user_data_dir().join("saves"))
+    }
+}
+
+impl SaveDir {
+    pub fn slot_path(&self, slot: &str) -> PathBuf {
+        self.0.join(format!("{}.json", slot))
+    }
+
+    pub fn meta_path(&self, slot: &str) -> PathBuf {
+        self.0.join(format!("{}{}", slot, META_SUFFIX))
+    }
+
+    /// Sloty z metadanymi, od ostatnio zapisanego (bez czytania pełnych zapisów)
+    pub fn list_slots(&self) -> Vec<(String, SlotMeta)> {
+        let Ok(entries) = fs::read_dir(&self.0) else {
+            return Vec::new();
+        };
+        let mut slots: Vec<(String, SlotMeta)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                let slot = file_name.strip_suffix(META_SUFFIX)?.to_string();
+                match SlotMeta::read(&entry.path()) {
+                    Ok(meta) => Some((slot, meta)),
+                    Err(err) => {
+                        warn!("Pomijam uszkodzone metadane slotu {}: {}", slot, err);
+                        None
+                    }
+                }
+            })
+            .collect();
+        slots.sort_by_key(|(_, meta)| std::cmp::Reverse(meta.saved_at));
+        slots
+    }
+
+    /// Pierwsza wolna nazwa slotu (slot_1, slot_2, ...)
+    pub fn free_slot(&self) -> String {
+        (1..)
+            .map(|i| format!("slot_{}", i))
+            .find(|slot| !self.slot_path(slot).exists() && !self.meta_path(slot).exists())
+            .unwrap()
+    }
+
+    pub fn delete_slot(&self, slot: &str) -> Result<(), SaveError> {
+        let backups = (1..=MAX_BACKUPS).map(|index| self.backup_path(slot, index));
+        for path in [self.slot_path(slot), self.meta_path(slot)].into_iter().chain(backups) {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn backup_path(&self, slot: &str, index: usize) -> PathBuf {
+        self.0.join(BACKUP_DIR).join(format!("{}.{}.json", slot, index))
+    }
+
+    /// Przesuwa kopie zapasowe (1 -> 2 -> ...) i odkłada obecny zapis slotu jako kopię 1
+    fn rotate_backups(&self, slot: &str) -> Result<(), SaveError> {
+        let current = self.slot_path(slot);
+        if !current.exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(self.0.join(BACKUP_DIR))?;
+        for index in (1..MAX_BACKUPS).rev() {
+            let from = self.backup_path(slot, index);
+            if from.exists() {
+                fs::rename(&from, self.backup_path(slot, index + 1))?;
+            }
+        }
+        fs::copy(&current, self.backup_path(slot, 1))?;
+        Ok(())
+    }
+
+    /// Zapisuje grę i metadane slotu; błędy tylko loguje, zwraca czy się udało
+    pub fn write_slot(&self, slot: &str, save: &SaveFile) -> bool {
+        // nazwa nadana przez gracza przeżywa nadpisanie slotu
+        let meta_path = self.meta_path(slot);
+        let meta = SlotMeta {
+            name: SlotMeta::read(&meta_path).map(|meta| meta.name).unwrap_or_else(|_| slot.to_string()),
+            playtime: save.playtime,
+            score: save.score,
+            saved_at: unix_now(),
+            seed: save.seed.0,
+        };
+        let path = self.slot_path(slot);
+        if let Err(err) = self.rotate_backups(slot) {
+            warn!("Nie udało się zrobić kopii zapasowej {}: {}", path.display(), err);
+        }
+        let result = save.write(&path).and_then(|()| write_atomic(&meta_path, &serde_json::to_string_pretty(&meta)?));
+        match result {
+            Ok(()) => {
+                info!("Zapisano grę: {}", path.display());
+                true
+            }
+            Err(err) => {
+                error!("Nie udało się zapisać gry {}: {}", path.display(), err);
+                false
+            }
+        }
+    }
+
+    /// Zmienia nazwę wyświetlaną slotu (plik slotu zostaje ten sam)
+    pub fn rename_slot(&self, slot: &str, name: &str) -> Result<(), SaveError> {
+        let path = self.meta_path(slot);
+        let mut meta = SlotMeta::read(&path)?;
+        meta.name = name.to_string();
+        write_atomic(&path, &serde_json::to_string_pretty(&meta)?)
+    }
+}
+
+/// Metadane slotu trzymane obok zapisu - wystarczają do listy slotów
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SlotMeta {
+    pub name: String,
+    /// czas gry w sekundach
+    pub playtime: f64,
+    pub score: u32,
+    /// chwila zapisu (sekundy od epoki unixowej)
+    pub saved_at: u64,
+    pub seed: u64,
+}
+
+impl SlotMeta {
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// Slot, do którego zapisuje bieżąca gra
+#[derive(Resource)]
+pub struct CurrentSlot(pub String);
+
+impl Default for CurrentSlot {
+    fn default() -> Self {
+        Self(DEFAULT_SLOT.to_string())
+    }
+}
+
+/// Łączny czas gry w sekundach (bez pauzy)
+#[derive(Resource, Default)]
+pub struct PlayTime(pub f64);
+
+/// Katalog danych podany z linii poleceń (--config-dir)
+static USER_DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Zastępuje katalog danych użytkownika - tylko przed pierwszym użyciem
+pub fn set_user_data_dir(path: PathBuf) {
+    if USER_DATA_DIR.set(path).is_err() {
+        warn!("Katalog danych użytkownika został już ustawiony");
+    }
+}
+
+/// Katalog danych użytkownika (XDG na Linuksie, APPDATA na Windowsie), awaryjnie katalog roboczy
+pub fn user_data_dir() -> PathBuf {
+    if let Some(dir) = USER_DATA_DIR.get() {
+        return dir.clone();
+    }
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.unwrap_or_else(|| PathBuf::from(".")).join(GAME_DIR)
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    MissingVersion,
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "błąd pliku: {}", err),
+            SaveError::Json(err) => write!(f, "błąd formatu: {}", err),
+            SaveError::MissingVersion => write!(f, "brak wersji zapisu"),
+            SaveError::UnsupportedVersion(version) => write!(f, "nieobsługiwana wersja zapisu {} (obsługiwana {})", version, SAVE_VERSION),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Json(err)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SavedTile {
+    pub pos: [i32; 2],
+    pub state: TileState,
+}
+
+/// Przedmiot zapisany po id - reszta danych pochodzi z aktualnego items.json
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SavedItem {
+    pub slot: u32,
+    pub id: String,
+    pub amount: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SavedPlayer {
+    pub position: [f32; 2],
+    pub health: f32,
+    pub max_health: f32,
+    pub stamina: f32,
+    pub inventory: Vec<SavedItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SavedMonster {
+    pub position: [f32; 2],
+    pub health: f32,
+}
+
+/// Zawartość pliku zapisu
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SaveFile {
+    pub version: u32,
+    pub seed: WorldSeed,
+    pub world_diff: Vec<SavedTile>,
+    pub player: SavedPlayer,
+    pub score: u32,
+    pub monsters: Vec<SavedMonster>,
+    pub clock: GameClock,
+    pub sim_tick: u64,
+    pub discoveries: Vec<[i32; 2]>,
+    pub playtime: f64,
+}
+
+impl SaveFile {
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parsuje zapis dowolnej obsługiwanej wersji, migrując go do aktualnej
+    pub fn from_json(data: &str) -> Result<Self, SaveError> {
+        let mut value: Value = serde_json::from_str(data)?;
+        let version = value
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or(SaveError::MissingVersion)? as u32;
+        if version == 0 || version > SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+        for migrate in &MIGRATIONS[(version - 1) as usize..] {
+            migrate(&mut value);
+        }
+        value["version"] = Value::from(SAVE_VERSION);
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        write_atomic(path, &self.to_json()?)
+    }
+}
+
+/// Zapis atomowy: plik tymczasowy + rename, więc przerwany zapis nie psuje starego
+pub(crate) fn write_atomic(path: &Path, data: &str) -> Result<(), SaveError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp)?;
+    file.write_all(data.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Zasoby świata, które trafiają do zapisu i są resetowane przy nowej grze
+#[derive(SystemParam)]
+pub struct WorldState<'w> {
+    pub seed: ResMut<'w, WorldSeed>,
+    pub world_diff: ResMut<'w, WorldDiff>,
+    pub clock: ResMut<'w, GameClock>,
+    pub sim_clock: ResMut<'w, TileSimClock>,
+    pub score: ResMut<'w, Score>,
+    pub discoveries: ResMut<'w, Discoveries>,
+    pub playtime: ResMut<'w, PlayTime>,
+}
+
+/// Wszystko, co trafia do zapisu
+#[derive(SystemParam)]
+pub struct GameSnapshot<'w, 's> {
+    world: WorldState<'w>,
+    player_query: Query<'w, 's, (&'static Transform, &'static PlayerData), With<Player>>,
+    monster_query: Query<'w, 's, (&'static Transform, &'static MonsterAI), With<Monster>>,
+}
+
+impl GameSnapshot<'_, '_> {
+    /// Stan gry do zapisu (None, gdy nie ma gracza - np. po śmierci)
+    pub fn capture(&self) -> Option<SaveFile> {
+        let (transform, player_data) = self.player_query.single().ok()?;
+        let world = &self.world;
+        let mut inventory: Vec<SavedItem> = player_data
+            .inventory
+            .items
+            .iter()
+            .map(|(&slot, item)| SavedItem { slot, id: item.id.clone(), amount: item.amount })
+            .collect();
+        inventory.sort_by_key(|item| item.slot);
+
+        Some(SaveFile {
+            version: SAVE_VERSION,
+            seed: *world.seed,
+            world_diff: world.world_diff.tiles
+                .iter()
+                .map(|(pos, state)| SavedTile { pos: pos.to_array(), state: *state })
+                .collect(),
+            player: SavedPlayer {
+                position: transform.translation.xy().to_array(),
+                health: player_data.health,
+                max_health: player_data.max_health,
+                stamina: player_data.satamina,
+                inventory,
+            },
+            score: world.score.0,
+            monsters: self.monster_query
+                .iter()
+                .map(|(transform, ai)| SavedMonster { position: transform.translation.xy().to_array(), health: ai.health })
+                .collect(),
+            clock: world.clock.clone(),
+            sim_tick: world.sim_clock.tick,
+            discoveries: world.discoveries.0.iter().map(|pos| pos.to_array()).collect(),
+            playtime: world.playtime.0,
+        })
+    }
+}
+
+/// Zasoby potrzebne do postawienia gracza i potworów
+#[derive(SystemParam)]
+pub struct SpawnAssets<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    asset_server: Res<'w, AssetServer>,
+    texture_atlas_layouts: ResMut<'w, Assets<TextureAtlasLayout>>,
+    item_config: Res<'w, ItemConfig>,
+    atlas_handles: Res<'w, AtlasHandles>,
+}
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveDir>()
+            .init_resource::<CurrentSlot>()
+            .init_resource::<PlayTime>()
+            .add_message::<NewGame>()
+            .add_message::<SaveGame>()
+            .add_message::<LoadGame>()
+            .add_message::<GameSaved>()
+            .add_systems(Update, (
+                tick_playtime.run_if(in_state(AppState::Playing)),
+                save_game.run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))),
+                start_game.run_if(not(in_state(AppState::Loading))),
+            ));
+    }
+}
+
+fn tick_playtime(time: Res<Time>, mut playtime: ResMut<PlayTime>) {
+    playtime.0 += time.delta_secs_f64();
+}
+
+fn save_game(
+    mut requests: MessageReader<SaveGame>,
+    save_dir: Res<SaveDir>,
+    snapshot: GameSnapshot,
+    mut saved: MessageWriter<GameSaved>,
+) {
+    for request in requests.read() {
+        let Some(save) = snapshot.capture() else {
+            warn!("Brak gracza - pomijam zapis {}", request.slot);
+            continue;
+        };
+        if save_dir.write_slot(&request.slot, &save) {
+            saved.write(GameSaved { slot: request.slot.clone() });
+        }
+    }
+}
+
+/// Nowa gra albo wczytanie zapisu: czyści świat i stawia gracza oraz potwory od nowa
+fn start_game(
+    mut commands: Commands,
+    mut new_games: MessageReader<NewGame>,
+    mut loads: MessageReader<LoadGame>,
+    save_dir: Res<SaveDir>,
+    mut current_slot: ResMut<CurrentSlot>,
+    mut world: WorldState,
+    mut terrain_map: ResMut<TerrainMap>,
+    mut structure_map: ResMut<StructureMap>,
+    mut assets: SpawnAssets,
+    world_entities: Query<Entity, Or<(With<Player>, With<Monster>, With<Floor>, With<Wall>, With<TriggerZone>)>>,
+    autosave: Option<ResMut<Autosave>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    // ostatnie żądanie wygrywa; bez zapisu = nowa gra z podanym seedem; slot None = bez zmiany
+    let mut start: Option<(Option<String>, u64, Option<SaveFile>)> = None;
+    for request in new_games.read() {
+        let slot = request.slot.clone().unwrap_or_else(|| save_dir.free_slot());
+        start = Some((Some(slot), request.seed.unwrap_or_else(rand::random::<u64>), None));
+    }
+    for request in loads.read() {
+        let path = save_dir.slot_path(&request.slot);
+        let slot = (!request.keep_slot).then(|| request.slot.clone());
+        match SaveFile::read(&path) {
+            Ok(save) => start = Some((slot, save.seed.0, Some(save))),
+            Err(err) => error!("Nie udało się wczytać gry {}: {}", path.display(), err),
+        }
+    }
+    let Some((slot, seed, save)) = start else {
+        return;
+    };
+    if let Some(slot) = slot {
+        current_slot.0 = slot;
+    }
+    // pełne odliczanie od startu gry
+    if let Some(mut autosave) = autosave {
+        *autosave = Autosave::default();
+    }
+
+    for entity in &world_entities {
+        commands.entity(entity).despawn();
+    }
+    for (_, entities) in structure_map.spawned.drain() {
+        for entity in entities {
+            commands.entity(entity).try_despawn();
+        }
+    }
+    *structure_map = StructureMap::default();
+    // teren wygeneruje się od nowa wokół gracza
+    *terrain_map = TerrainMap::default();
+
+    *world.seed = WorldSeed(seed);
+    world.world_diff.tiles.clear();
+    *world.clock = GameClock::default();
+    world.sim_clock.tick = 0;
+    world.score.0 = 0;
+    world.discoveries.0.clear();
+    world.playtime.0 = 0.0;
+
+    let player = crate::systems::player::init(
+        &mut commands,
+        &mut assets.meshes,
+        &assets.asset_server,
+        &mut assets.texture_atlas_layouts,
+        &assets.item_config,
+        &assets.atlas_handles,
+    );
+
+    if let Some(save) = save {
+        apply_save(&mut commands, &save, player, &mut world, &mut assets);
+    }
+
+    next_state.set(AppState::Playing);
+}
+
+/// Przywraca stan z zapisu na świeżo wyczyszczony świat
+fn apply_save(
+    commands: &mut Commands,
+    save: &SaveFile,
+    player: Entity,
+    world: &mut WorldState,
+    assets: &mut SpawnAssets,
+) {
+    world.world_diff.tiles = save.world_diff
+        .iter()
+        .map(|tile| (IVec2::from_array(tile.pos), tile.state))
+        .collect();
+    *world.clock = save.clock.clone();
+    world.sim_clock.tick = save.sim_tick;
+    world.score.0 = save.score;
+    world.discoveries.0 = save.discoveries.iter().map(|pos| IVec2::from_array(*pos)).collect();
+    world.playtime.0 = save.playtime;
+
+    let mut player_data = PlayerData::new(&assets.item_config);
+    player_data.health = save.player.health;
+    player_data.max_health = save.player.max_health;
+    player_data.satamina = save.player.stamina;
+    player_data.inventory.items.clear();
+    for saved in &save.player.inventory {
+        let Some(item) = assets.item_config.items.get(&saved.id) else {
+            warn!("Nieznany przedmiot w zapisie: {}", saved.id);
+            continue;
+        };
+        player_data.inventory.items.insert(saved.slot, Item { amount: saved.amount, ..item.clone() });
+    }
+    let [x, y] = save.player.position;
+    commands.entity(player).insert((player_data, Transform::from_xyz(x, y, PLAYER_Z)));
+
+    for monster in &save.monsters {
+        spawn_monster(
+            commands,
+            &assets.asset_server,
+            &mut assets.texture_atlas_layouts,
+            &mut assets.meshes,
+            &assets.atlas_handles,
+            Vec2::from_array(monster.position),
+            monster.health,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    /// Zapis w wersji 1 - bez czasu gry
+    const SAVE_V1: &str = r#"{
+        "version": 1,
+        "seed": 42,
+        "world_diff": [{ "pos": [3, -2], "state": { "Burning": { "ticks": 2 } } }],
+        "player": {
+            "position": [128.0, -64.0],
+            "health": 80.0,
+            "max_health": 100.0,
+            "stamina": 50.0,
+            "inventory": [{ "slot": 0, "id": "sword", "amount": 1 }]
+        },
+        "score": 7,
+        "monsters": [{ "position": [10.0, 20.0], "health": 30.0 }],
+        "clock": { "day": 3, "time_of_day": 0.5 },
+        "sim_tick": 120,
+        "discoveries": [[1, 1]]
+    }"#;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mgla_2d_game_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn item_config() -> ItemConfig {
+        serde_json::from_str(&fs::read_to_string("assets/config/items.json").unwrap()).unwrap()
+    }
+
+    /// Świat z zasobami i encjami, które trafiają do zapisu
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldSeed(1234));
+        world.insert_resource(WorldDiff {
+            tiles: [(IVec2::new(1, 2), TileState::Burned { ticks: 4 }), (IVec2::new(-5, 0), TileState::Snowy)].into(),
+        });
+        world.insert_resource(GameClock { day: 2, time_of_day: 0.75 });
+        world.insert_resource(TileSimClock { tick: 99 });
+        world.insert_resource(Score(5));
+        world.insert_resource(Discoveries([IVec2::new(4, 4)].into()));
+        world.insert_resource(PlayTime(321.5));
+        world.insert_resource(item_config());
+        world.run_system_once(|mut commands: Commands, config: Res<ItemConfig>| {
+            let mut player_data = PlayerData::new(&config);
+            player_data.health = 42.0;
+            commands.spawn((Player, player_data, Transform::from_xyz(10.0, -20.0, PLAYER_Z)));
+        }).unwrap();
+        world.spawn((
+            Monster,
+            MonsterAI {
+                target_player: false,
+                sees_player: false,
+                random_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
+                random_dir: Vec2::ZERO,
+                action_timer: Timer::from_seconds(0.1, TimerMode::Once),
+                action_cooldown: Timer::from_seconds(2.0, TimerMode::Once),
+                health: 15.0,
+            },
+            Transform::from_xyz(-100.0, 50.0, 0.0),
+        ));
+        world
+    }
+
+    #[test]
+    fn capture_round_trips_through_json() {
+        let mut world = test_world();
+        let save = world.run_system_once(|snapshot: GameSnapshot| snapshot.capture()).unwrap().expect("brak gracza");
+        let json = save.to_json().unwrap();
+        let read = SaveFile::from_json(&json).unwrap();
+
+        assert_eq!(read.to_json().unwrap(), json);
+        assert_eq!(read.seed.0, 1234);
+        assert_eq!(read.player.position, [10.0, -20.0]);
+        assert_eq!(read.player.health, 42.0);
+        assert_eq!(read.world_diff.len(), 2);
+        assert_eq!(read.monsters.len(), 1);
+        assert_eq!(read.monsters[0].health, 15.0);
+        assert_eq!(read.sim_tick, 99);
+        assert_eq!(read.playtime, 321.5);
+    }
+
+    #[test]
+    fn capture_without_player_is_none() {
+        let mut world = test_world();
+        let player = world.query_filtered::<Entity, With<Player>>().single(&world).unwrap();
+        world.despawn(player);
+        assert!(world.run_system_once(|snapshot: GameSnapshot| snapshot.capture()).unwrap().is_none());
+    }
+
+    #[test]
+    fn v1_save_is_migrated() {
+        let save = SaveFile::from_json(SAVE_V1).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.playtime, 0.0);
+        assert_eq!(save.seed.0, 42);
+        assert_eq!(save.score, 7);
+        assert_eq!(save.player.inventory[0].id, "sword");
+        assert_eq!(save.world_diff[0].state, TileState::Burning { ticks: 2 });
+    }
+
+    #[test]
+    fn unknown_versions_are_rejected() {
+        let future = SAVE_V1.replace("\"version\": 1", &format!("\"version\": {}", SAVE_VERSION + 1));
+        assert!(matches!(SaveFile::from_json(&future), Err(SaveError::UnsupportedVersion(_))));
+        let zero = SAVE_V1.replace("\"version\": 1", "\"version\": 0");
+        assert!(matches!(SaveFile::from_json(&zero), Err(SaveError::UnsupportedVersion(0))));
+        let missing = SAVE_V1.replace("\"version\": 1,", "");
+        assert!(matches!(SaveFile::from_json(&missing), Err(SaveError::MissingVersion)));
+    }
+
+    #[test]
+    fn write_atomic_leaves_no_tmp_file() {
+        let dir = test_dir("atomic");
+        let path = dir.join("slot.json");
+        write_atomic(&path, "pierwszy").unwrap();
+        write_atomic(&path, "drugi").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "drugi");
+        let files: Vec<_> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        assert_eq!(files, ["slot.json"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,310 @@
+use bevy::prelude::*;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use crate::resourses::physics_resources::*;
+use crate::systems::menu_ui::{spawn_button, MenuAssets};
+use crate::systems::cli::LaunchOptions;
+use crate::systems::save::{SaveDir, SlotMeta};
+use crate::systems::settings::Settings;
+
+pub struct SaveSlotsPlugin;
+
+const MAX_NAME_LEN: usize = 24;
+
+/// Przyciski listy slotów
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SlotAction {
+    Load,
+    NewGameHere,
+    Rename,
+    Delete,
+}
+
+#[derive(Component)]
+struct SlotButton {
+    slot: String,
+    action: SlotAction,
+}
+
+#[derive(Component)]
+enum BrowserButton {
+    NewSlot,
+    Back,
+}
+
+/// Opis slotu w wierszu listy
+#[derive(Component)]
+struct SlotLabel {
+    slot: String,
+    meta: SlotMeta,
+}
+
+#[derive(Component)]
+struct SlotBrowserRoot;
+
+/// Slot, któremu gracz właśnie wpisuje nową nazwę
+#[derive(Resource, Default)]
+struct RenamingSlot(Option<(String, String)>);
+
+impl Plugin for SaveSlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RenamingSlot>()
+            .add_systems(OnEnter(MenuScreen::SaveSlots), spawn_slot_browser)
+            .add_systems(OnExit(MenuScreen::SaveSlots), |mut renaming: ResMut<RenamingSlot>| renaming.0 = None)
+            .add_systems(Update, (
+                slot_button_system,
+                browser_button_system,
+                rename_input,
+            ).chain().run_if(in_state(MenuScreen::SaveSlots)));
+    }
+}
+
+/// "1h 05m" albo "12m 30s"
+fn format_playtime(seconds: f64) -> String {
+    let total = seconds as u64;
+    let (hours, minutes, secs) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m {:02}s", minutes, secs)
+    }
+}
+
+/// Data zapisu "RRRR-MM-DD GG:MM" (UTC) z sekund od epoki unixowej
+fn format_date(unix_secs: u64) -> String {
+    let days = (unix_secs / 86400) as i64;
+    let (hour, minute) = (unix_secs / 3600 % 24, unix_secs / 60 % 60);
+    // algorytm civil_from_days (H. Hinnant)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, hour, minute)
+}
+
+fn slot_label(meta: &SlotMeta, name: &str) -> String {
+    format!(
+        "{}  |  {}  |  {} pts  |  {}  |  seed {}",
+        name,
+        format_playtime(meta.playtime),
+        meta.score,
+        format_date(meta.saved_at),
+        meta.seed,
+    )
+}
+
+fn spawn_slot_browser(mut commands: Commands, menu_assets: Res<MenuAssets>, save_dir: Res<SaveDir>, settings: Res<Settings>) {
+    build_slot_browser(&mut commands, &menu_assets, &save_dir, &settings);
+}
+
+fn build_slot_browser(commands: &mut Commands, menu_assets: &MenuAssets, save_dir: &SaveDir, settings: &Settings) {
+    let font = menu_assets.font.clone();
+    let language = settings.language;
+    let slots = save_dir.list_slots();
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        ImageNode::new(menu_assets.background.clone()),
+        MenuRoot,
+        SlotBrowserRoot,
+        DespawnOnExit(MenuScreen::SaveSlots),
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new(language.text("Save Slots")),
+            TextFont { font: font.clone(), font_size: 40.0, ..default() },
+            TextColor(Color::WHITE),
+            Node { margin: UiRect::bottom(Val::Px(20.0)), ..default() },
+        ));
+
+        if slots.is_empty() {
+            parent.spawn((
+                Text::new(language.text("No saved games")),
+                TextFont { font: font.clone(), font_size: 24.0, ..default() },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        }
+
+        for (slot, meta) in &slots {
+            parent.spawn(Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|row| {
+                row.spawn((
+                    Text::new(slot_label(meta, &meta.name)),
+                    TextFont { font: font.clone(), font_size: 20.0, ..default() },
+                    TextColor(Color::WHITE),
+                    Node { width: Val::Px(620.0), ..default() },
+                    SlotLabel { slot: slot.clone(), meta: meta.clone() },
+                ));
+                for (action, label) in [
+                    (SlotAction::Load, "Load"),
+                    (SlotAction::NewGameHere, "New Game"),
+                    (SlotAction::Rename, "Rename"),
+                    (SlotAction::Delete, "Delete"),
+                ] {
+                    spawn_button(row, &font, language.text(label), SlotButton { slot: slot.clone(), action });
+                }
+            });
+        }
+
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Row,
+            margin: UiRect::top(Val::Px(20.0)),
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_button(row, &font, language.text("New Slot"), BrowserButton::NewSlot);
+            spawn_button(row, &font, language.text("Back"), BrowserButton::Back);
+        });
+    });
+}
+
+fn rebuild_slot_browser(
+    commands: &mut Commands,
+    roots: &Query<Entity, With<SlotBrowserRoot>>,
+    menu_assets: &MenuAssets,
+    save_dir: &SaveDir,
+    settings: &Settings,
+) {
+    for root in roots {
+        commands.entity(root).despawn();
+    }
+    build_slot_browser(commands, menu_assets, save_dir, settings);
+}
+
+fn slot_button_system(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &SlotButton), Changed<Interaction>>,
+    roots: Query<Entity, With<SlotBrowserRoot>>,
+    menu_assets: Res<MenuAssets>,
+    save_dir: Res<SaveDir>,
+    settings: Res<Settings>,
+    mut renaming: ResMut<RenamingSlot>,
+    mut new_game: MessageWriter<NewGame>,
+    mut load_game: MessageWriter<LoadGame>,
+    launch: Res<LaunchOptions>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button.action {
+            SlotAction::Load => {
+                load_game.write(LoadGame { slot: button.slot.clone(), keep_slot: false });
+            }
+            SlotAction::NewGameHere => {
+                new_game.write(NewGame { seed: launch.seed, slot: Some(button.slot.clone()) });
+            }
+            SlotAction::Rename => {
+                let name = SlotMeta::read(&save_dir.meta_path(&button.slot)).map(|meta| meta.name).unwrap_or_default();
+                renaming.0 = Some((button.slot.clone(), name));
+            }
+            SlotAction::Delete => {
+                if let Err(err) = save_dir.delete_slot(&button.slot) {
+                    error!("Nie udało się usunąć slotu {}: {}", button.slot, err);
+                }
+                renaming.0 = None;
+                rebuild_slot_browser(&mut commands, &roots, &menu_assets, &save_dir, &settings);
+            }
+        }
+    }
+}
+
+fn browser_button_system(
+    interaction_query: Query<(&Interaction, &BrowserButton), Changed<Interaction>>,
+    mut new_game: MessageWriter<NewGame>,
+    mut next_screen: ResMut<NextState<MenuScreen>>,
+    launch: Res<LaunchOptions>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            BrowserButton::NewSlot => {
+                new_game.write(NewGame { seed: launch.seed, slot: None });
+            }
+            BrowserButton::Back => {
+                next_screen.set(MenuScreen::Main);
+            }
+        }
+    }
+}
+
+/// Wpisywanie nazwy slotu: Enter zatwierdza, Escape anuluje
+fn rename_input(
+    mut commands: Commands,
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut renaming: ResMut<RenamingSlot>,
+    mut labels: Query<(&SlotLabel, &mut Text)>,
+    roots: Query<Entity, With<SlotBrowserRoot>>,
+    menu_assets: Res<MenuAssets>,
+    save_dir: Res<SaveDir>,
+    settings: Res<Settings>,
+) {
+    let Some((slot, buffer)) = renaming.0.as_mut() else {
+        keyboard.clear();
+        return;
+    };
+    let mut done = None;
+    for event in keyboard.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => done = Some(true),
+            Key::Escape => done = Some(false),
+            Key::Backspace => {
+                buffer.pop();
+            }
+            Key::Space if buffer.chars().count() < MAX_NAME_LEN => buffer.push(' '),
+            Key::Character(text) => {
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    if buffer.chars().count() < MAX_NAME_LEN {
+                        buffer.push(c);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match done {
+        Some(true) if !buffer.trim().is_empty() => {
+            if let Err(err) = save_dir.rename_slot(slot, buffer.trim()) {
+                error!("Nie udało się zmienić nazwy slotu {}: {}", slot, err);
+            }
+            renaming.0 = None;
+            rebuild_slot_browser(&mut commands, &roots, &menu_assets, &save_dir, &settings);
+        }
+        Some(_) => {
+            renaming.0 = None;
+            rebuild_slot_browser(&mut commands, &roots, &menu_assets, &save_dir, &settings);
+        }
+        None => {
+            for (label, mut text) in &mut labels {
+                if label.slot != *slot {
+                    continue;
+                }
+                let edited = slot_label(&label.meta, &format!("{}_", buffer));
+                if text.0 != edited {
+                    text.0 = edited;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use crate::resourses::physics_resources::*;
+use crate::systems::terrain::{FloorSprites, TerrainMap};
+
+pub struct SeasonPlugin;
+
+impl Plugin for SeasonPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GameClock::default())
+            .add_systems(Update, (advance_clock, apply_season, freeze_water).chain()
+                .run_if(in_state(AppState::Playing)));
+    }
+}
+
+fn advance_clock(
+    time: Res<Time>,
+    mut clock: ResMut<GameClock>,
+) {
+    clock.time_of_day += time.delta_secs() / DAY_SECONDS;
+    while clock.time_of_day >= 1.0 {
+        clock.time_of_day -= 1.0;
+        clock.day += 1;
+    }
+}
+
+/// Po zmianie pory roku: pogoda i paleta trawy na już wygenerowanym terenie
+fn apply_season(
+    clock: Res<GameClock>,
+    mut weather: ResMut<Weather>,
+    terrain_map: Res<TerrainMap>,
+    mut floor_sprites: FloorSprites,
+) {
+    let season = clock.season();
+    if weather.cold == season.is_cold() && weather.grass_tint == season.grass_tint() {
+        return;
+    }
+    weather.cold = season.is_cold();
+    weather.grass_tint = season.grass_tint();
+
+    for (pos, cell) in terrain_map.sim_cells.iter() {
+        let Some(&floor) = terrain_map.floors.get(pos) else {
+            continue;
+        };
+        floor_sprites.set_look(floor, cell.look(&weather));
+    }
+}
+
+/// Zimą woda zamarza - lód jest widoczny i można po nim chodzić
+fn freeze_water(
+    mut commands: Commands,
+    weather: Res<Weather>,
+    water_query: Query<(Entity, &RigidBodyHandleComponent, &Children, Has<Frozen>), With<Water>>,
+    mut ice_query: Query<&mut Visibility, With<IceSprite>>,
+    rigid_bodies: Res<ResRigidBodySet>,
+    mut colliders: ResMut<ResColliderSet>,
+) {
+    for (entity, handle, children, frozen) in &water_query {
+        if frozen == weather.cold {
+            continue;
+        }
+        if let Some(rb) = rigid_bodies.0.get(handle.0) {
+            for collider_handle in rb.colliders() {
+                if let Some(collider) = colliders.0.get_mut(*collider_handle) {
+                    collider.set_enabled(!weather.cold);
+                }
+            }
+        }
+        for child in children.iter() {
+            if let Ok(mut visibility) = ice_query.get_mut(child) {
+                *visibility = if weather.cold { Visibility::Inherited } else { Visibility::Hidden };
+            }
+        }
+        if weather.cold {
+            commands.entity(entity).insert(Frozen);
+        } else {
+            commands.entity(entity).remove::<Frozen>();
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use bevy::prelude::*;
+use bevy::audio::Volume;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, VideoModeSelection, WindowMode, WindowResolution};
+use crate::systems::actions::InputMap;
+use crate::systems::save::{user_data_dir, write_atomic, SaveError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+pub struct SettingsPlugin;
+
+const SETTINGS_FILE: &str = "settings.json";
+pub const RESOLUTIONS: [[u32; 2]; 4] = [[1280, 720], [1600, 900], [1920, 1080], [2560, 1440]];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowModeSetting {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    English,
+    Polish,
+}
+
+impl Language {
+    /// Tłumaczenie napisu interfejsu (klucz = tekst angielski)
+    pub fn text(self, english: &'static str) -> &'static str {
+        if self == Language::English {
+            return english;
+        }
+        match english {
+            "New Game" => "Nowa gra",
+            "Load Game" => "Wczytaj grę",
+            "Save Game" => "Zapisz grę",
+            "Resume" => "Wznów",
+            "Options" => "Opcje",
+            "Exit" => "Wyjdź",
+            "Game Over" => "Koniec gry",
+            "Save Slots" => "Sloty zapisu",
+            "No saved games" => "Brak zapisanych gier",
+            "Load" => "Wczytaj",
+            "Rename" => "Zmień nazwę",
+            "Delete" => "Usuń",
+            "New Slot" => "Nowy slot",
+            "Back" => "Wróć",
+            "Window mode" => "Tryb okna",
+            "Resolution" => "Rozdzielczość",
+            "VSync" => "Synchronizacja",
+            "UI scale" => "Skala interfejsu",
+            "Master volume" => "Głośność ogólna",
+            "Music volume" => "Głośność muzyki",
+            "SFX volume" => "Głośność efektów",
+            "Language" => "Język",
+            "Difficulty" => "Poziom trudności",
+            "Windowed" => "Okno",
+            "Borderless" => "Bez ramki",
+            "Fullscreen" => "Pełny ekran",
+            "On" => "Wł.",
+            "Off" => "Wył.",
+            "Easy" => "Łatwy",
+            "Normal" => "Normalny",
+            "Hard" => "Trudny",
+            "Saving..." => "Zapisywanie...",
+            "Controls" => "Sterowanie",
+            "Press a key or button (Esc cancels)" => "Naciśnij klawisz lub przycisk (Esc anuluje)",
+            "Reset" => "Domyślne",
+            "Keyboard / mouse" => "Klawiatura / mysz",
+            "Gamepad" => "Pad",
+            "None" => "Brak",
+            "Move up" => "Ruch w górę",
+            "Move down" => "Ruch w dół",
+            "Move left" => "Ruch w lewo",
+            "Move right" => "Ruch w prawo",
+            "Sprint" => "Bieg",
+            "Attack" => "Atak",
+            "Use item" => "Użyj przedmiotu",
+            "Next slot" => "Następny slot",
+            "Pause" => "Pauza",
+            "Interact" => "Interakcja",
+            "Quicksave" => "Szybki zapis",
+            "Quickload" => "Szybki odczyt",
+            "Debug overlay" => "Nakładka debugowa",
+            _ => english,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Mnożnik obrażeń zadawanych graczowi przez potwory
+    pub fn damage_multiplier(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+}
+
+/// Ustawienia gracza zapisywane w katalogu danych użytkownika
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Settings {
+    pub window_mode: WindowModeSetting,
+    pub resolution: [u32; 2],
+    pub vsync: bool,
+    pub ui_scale: f32,
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub language: Language,
+    pub difficulty: Difficulty,
+    pub controls: InputMap,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowModeSetting::Borderless,
+            resolution: [1920, 1080],
+            vsync: true,
+            ui_scale: 1.0,
+            master_volume: 1.0,
+            music_volume: 0.8,
+            sfx_volume: 1.0,
+            language: Language::English,
+            difficulty: Difficulty::Normal,
+            controls: InputMap::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn path() -> PathBuf {
+        user_data_dir().join(SETTINGS_FILE)
+    }
+
+    /// Ustawienia z pliku; brak pliku albo błąd = domyślne
+    pub fn load() -> Self {
+        let path = Self::path();
+        let Ok(data) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        let mut settings: Self = serde_json::from_str(&data).unwrap_or_else(|err| {
+            warn!("Błędny plik ustawień {}: {}", path.display(), err);
+            Self::default()
+        });
+        settings.controls.fill_missing();
+        settings
+    }
+
+    pub fn save(&self) -> Result<(), SaveError> {
+        write_atomic(&Self::path(), &serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn window_mode(&self) -> WindowMode {
+        match self.window_mode {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
+            WindowModeSetting::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Primary, VideoModeSelection::Current),
+        }
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync }
+    }
+
+    /// Głośność kanału (bez głośności ogólnej, którą dokłada GlobalVolume)
+    pub fn channel_volume(&self, channel: AudioChannel) -> f32 {
+        match channel {
+            AudioChannel::Music => self.music_volume,
+            AudioChannel::Sfx => self.sfx_volume,
+        }
+    }
+
+    /// Główne okno zbudowane z ustawień (przy starcie)
+    pub fn window(&self) -> Window {
+        Window {
+            resolution: WindowResolution::new(self.resolution[0], self.resolution[1]),
+            mode: self.window_mode(),
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+}
+
+/// Kanał dźwięku - encje z AudioPlayer dostają głośność muzyki albo efektów
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AudioChannel {
+    Music,
+    Sfx,
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_systems(Update, (
+                apply_window_settings,
+                apply_ui_scale,
+                apply_audio_settings,
+                save_settings.run_if(not(resource_added::<Settings>)),
+            ).run_if(resource_changed::<Settings>))
+            .add_systems(PostUpdate, apply_new_sink_volumes);
+    }
+}
+
+/// Okno powstaje z ustawień i flag --windowed/--resolution, więc pierwsze uruchomienie
+/// tylko zapamiętuje ustawienia, a potem zmieniamy wyłącznie pola zmienione przez gracza
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut applied: Local<Option<Settings>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Some(previous) = applied.replace(settings.clone()) else {
+        return;
+    };
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    let mode_changed = settings.window_mode != previous.window_mode;
+    if mode_changed {
+        window.mode = settings.window_mode();
+    }
+    // w trybach pełnoekranowych rozmiar okna wyznacza monitor
+    let resized = mode_changed || settings.resolution != previous.resolution;
+    if settings.window_mode == WindowModeSetting::Windowed && resized {
+        let [width, height] = settings.resolution;
+        window.resolution.set_physical_resolution(width, height);
+    }
+    if settings.vsync != previous.vsync {
+        window.present_mode = settings.present_mode();
+    }
+}
+
+/// Bez UI i dźwięku (tryb headless) nie ma czego ustawiać
+fn apply_ui_scale(settings: Res<Settings>, ui_scale: Option<ResMut<UiScale>>) {
+    if let Some(mut ui_scale) = ui_scale {
+        ui_scale.0 = settings.ui_scale;
+    }
+}
+
+/// Głośność ogólna przez GlobalVolume, muzyka i efekty na odtwarzaczach ich kanałów
+fn apply_audio_settings(
+    settings: Res<Settings>,
+    global_volume: Option<ResMut<GlobalVolume>>,
+    mut sinks: Query<(&AudioChannel, &mut AudioSink)>,
+) {
+    if let Some(mut global_volume) = global_volume {
+        global_volume.volume = Volume::Linear(settings.master_volume);
+    }
+    for (channel, mut sink) in &mut sinks {
+        sink.set_volume(Volume::Linear(settings.channel_volume(*channel)));
+    }
+}
+
+/// Odtwarzacz powstaje dopiero po wczytaniu dźwięku - wtedy dostaje głośność swojego kanału
+fn apply_new_sink_volumes(
+    settings: Res<Settings>,
+    mut sinks: Query<(&AudioChannel, &mut AudioSink), Added<AudioSink>>,
+) {
+    for (channel, mut sink) in &mut sinks {
+        sink.set_volume(Volume::Linear(settings.channel_volume(*channel)));
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    if let Err(err) = settings.save() {
+        error!("Nie udało się zapisać ustawień {}: {}", Settings::path().display(), err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Okno jak po `--windowed --resolution 800x600` przy pełnoekranowych ustawieniach
+    fn window_app() -> App {
+        let mut app = App::new();
+        app.insert_resource(Settings {
+            window_mode: WindowModeSetting::Fullscreen,
+            resolution: [1920, 1080],
+            ..default()
+        })
+        .add_systems(Update, apply_window_settings.run_if(resource_changed::<Settings>));
+        app.world_mut().spawn((
+            Window { resolution: WindowResolution::new(800, 600), ..default() },
+            PrimaryWindow,
+        ));
+        app
+    }
+
+    fn window(app: &mut App) -> Window {
+        let mut query = app.world_mut().query_filtered::<&Window, With<PrimaryWindow>>();
+        query.single(app.world()).unwrap().clone()
+    }
+
+    #[test]
+    fn cli_overrides_survive_unrelated_changes() {
+        let mut app = window_app();
+        app.update();
+        app.world_mut().resource_mut::<Settings>().master_volume = 0.5;
+        app.update();
+        let window = window(&mut app);
+        assert_eq!(window.mode, WindowMode::Windowed);
+        assert_eq!(window.resolution.physical_width(), 800);
+        assert_eq!(window.resolution.physical_height(), 600);
+    }
+
+    #[test]
+    fn changed_fields_are_applied() {
+        let mut app = window_app();
+        app.update();
+        app.world_mut().resource_mut::<Settings>().vsync = false;
+        app.update();
+        assert_eq!(window(&mut app).present_mode, PresentMode::AutoNoVsync);
+        assert_eq!(window(&mut app).mode, WindowMode::Windowed);
+
+        // rozdzielczość dopiero po przejściu w okno
+        app.world_mut().resource_mut::<Settings>().resolution = [1280, 720];
+        app.update();
+        assert_eq!(window(&mut app).resolution.physical_width(), 800);
+        app.world_mut().resource_mut::<Settings>().window_mode = WindowModeSetting::Windowed;
+        app.update();
+        assert_eq!(window(&mut app).resolution.physical_width(), 1280);
+        assert_eq!(window(&mut app).resolution.physical_height(), 720);
+    }
+
+    #[test]
+    fn channel_volumes_round_trip_and_default() {
+        let settings = Settings { music_volume: 0.3, sfx_volume: 0.6, ..default() };
+        let loaded: Settings = serde_json::from_str(&serde_json::to_string(&settings).unwrap()).unwrap();
+        assert_eq!(loaded.channel_volume(AudioChannel::Music), 0.3);
+        assert_eq!(loaded.channel_volume(AudioChannel::Sfx), 0.6);
+
+        // plik sprzed kanałów dźwięku
+        let old: Settings = serde_json::from_str(r#"{ "master_volume": 0.5 }"#).unwrap();
+        assert_eq!(old.master_volume, 0.5);
+        assert_eq!(old.music_volume, Settings::default().music_volume);
+        assert_eq!(old.sfx_volume, Settings::default().sfx_volume);
+    }
+}
+
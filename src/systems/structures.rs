@@ -0,0 +1,314 @@
+use bevy::prelude::*;
+use bevy::camera::visibility::RenderLayers;
+use crate::resourses::physics_resources::*;
+use crate::systems::actions::{Action, ActionState};
+use crate::systems::terrain::TerrainMap;
+use crate::systems::tile_sim::{roll, SIM_SALT};
+use std::collections::{HashMap, HashSet};
+
+pub struct StructurePlugin;
+
+const STRUCTURE_SALT: u64 = 0x57_0000;
+
+/// Struktury postawione na wygenerowanym terenie (kafelek -> encje struktury i jej stref)
+#[derive(Resource, Default)]
+pub struct StructureMap {
+    pub spawned: HashMap<IVec2, Vec<Entity>>,
+    /// kafelki już sprawdzone (także te bez struktury)
+    checked: HashSet<IVec2>,
+}
+
+/// Kafelki odkrytych struktur
+#[derive(Resource, Default)]
+pub struct Discoveries(pub HashSet<IVec2>);
+
+impl Plugin for StructurePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StructureMap::default())
+            .insert_resource(Discoveries::default())
+            .add_message::<ZoneEntered>()
+            .add_message::<ZoneExited>()
+            .add_systems(Update, (
+                place_structures,
+                route_zone_events,
+                (heal_in_zones, trigger_traps, discover_structures, grant_rewards),
+            ).chain().run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Sensor strefy wyzwalanej w pozycji świata
+pub fn spawn_trigger_zone(commands: &mut Commands, pos: Vec2, radius: f32, effect: ZoneEffect) -> Entity {
+    commands.spawn((
+        TriggerZone { effect },
+        Pending,
+        PhysicsBody::Fixed,
+        BodyShape::Ball { radius },
+        ColliderProps { sensor: true, ..default() },
+        CollisionLayer::Sensor,
+        Transform::from_xyz(pos.x, pos.y, 0.0),
+    )).id()
+}
+
+/// Stawia strukturę z szablonu: sprite, opcjonalne światło i strefy
+pub fn spawn_structure(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    template: &StructureTemplate,
+    pos: Vec2,
+) -> Vec<Entity> {
+    let mut structure = commands.spawn((
+        Sprite {
+            image: asset_server.load(&template.texture),
+            custom_size: Some(Vec2::splat(template.size)),
+            ..default()
+        },
+        YSort { z: 0.3 },
+        Transform::from_xyz(pos.x, pos.y, -32.0),
+        RenderLayers::from_layers(CAMERA_LAYER_ENTITY),
+    ));
+    if let Some(light) = &template.light {
+        structure.with_child((
+            Transform::from_xyz(0.0, 0.0, 0.0),
+            LightSource {
+                range: light.range,
+                intensity: light.intensity,
+                color: Color::srgb(light.color[0], light.color[1], light.color[2]),
+            },
+            YSort { z: 0.0 },
+        ));
+    }
+    let mut entities = vec![structure.id()];
+    for zone in &template.zones {
+        let offset = Vec2::new(zone.offset[0], zone.offset[1]);
+        entities.push(spawn_trigger_zone(commands, pos + offset, zone.radius, zone.effect.clone()));
+    }
+    entities
+}
+
+/// Losuje struktury na nowych kafelkach podłogi i usuwa te z wyładowanego terenu
+fn place_structures(
+    mut commands: Commands,
+    terrain_map: Res<TerrainMap>,
+    config: Res<StructureConfig>,
+    mut structure_map: ResMut<StructureMap>,
+    world_seed: Res<WorldSeed>,
+    asset_server: Res<AssetServer>,
+) {
+    let unloaded: Vec<IVec2> = structure_map
+        .spawned
+        .keys()
+        .filter(|pos| !terrain_map.is_generated(**pos))
+        .copied()
+        .collect();
+    for pos in unloaded {
+        for entity in structure_map.spawned.remove(&pos).unwrap_or_default() {
+            commands.entity(entity).despawn();
+        }
+    }
+    structure_map.checked.retain(|pos| terrain_map.is_generated(*pos));
+
+    // kolejność szablonów stała, żeby losowanie było powtarzalne
+    let mut names: Vec<&String> = config.structures.keys().collect();
+    names.sort();
+
+    for &pos in &terrain_map.walkable {
+        if !structure_map.checked.insert(pos) {
+            continue;
+        }
+        let Some(biome) = terrain_map.biome_at(pos.as_vec2()) else {
+            continue;
+        };
+        for (i, name) in names.iter().enumerate() {
+            let template = &config.structures[*name];
+            if !template.biomes.contains(&biome) {
+                continue;
+            }
+            if roll(world_seed.sim_seed(SIM_SALT), pos, 0, STRUCTURE_SALT + i as u64) < template.chance {
+                let entities = spawn_structure(&mut commands, &asset_server, template, pos.as_vec2());
+                structure_map.spawned.insert(pos, entities);
+                break;
+            }
+        }
+    }
+}
+
+/// Zamienia zdarzenia sensorów na wejścia/wyjścia ze stref
+fn route_zone_events(
+    mut started: MessageReader<CollisionStarted>,
+    mut stopped: MessageReader<CollisionStopped>,
+    mut zones: Query<&mut ZoneOccupants, With<TriggerZone>>,
+    mut entered: MessageWriter<ZoneEntered>,
+    mut exited: MessageWriter<ZoneExited>,
+) {
+    for ev in started.read().filter(|ev| ev.sensor) {
+        for (zone, entity) in [(ev.a, ev.b), (ev.b, ev.a)] {
+            if let Ok(mut occupants) = zones.get_mut(zone)
+                && occupants.0.insert(entity)
+            {
+                entered.write(ZoneEntered { zone, entity });
+            }
+        }
+    }
+    for ev in stopped.read().filter(|ev| ev.sensor) {
+        for (zone, entity) in [(ev.a, ev.b), (ev.b, ev.a)] {
+            if let Ok(mut occupants) = zones.get_mut(zone)
+                && occupants.0.remove(&entity)
+            {
+                exited.write(ZoneExited { zone, entity });
+            }
+        }
+    }
+}
+
+fn heal_in_zones(
+    time: Res<Time>,
+    zones: Query<(&TriggerZone, &ZoneOccupants)>,
+    mut players: Query<&mut PlayerData, With<Player>>,
+) {
+    for (zone, occupants) in &zones {
+        let ZoneEffect::Heal { per_second } = zone.effect else {
+            continue;
+        };
+        for &entity in &occupants.0 {
+            if let Ok(mut pdata) = players.get_mut(entity)
+                && pdata.health > 0.0
+            {
+                pdata.health = (pdata.health + per_second * time.delta_secs()).min(pdata.max_health);
+            }
+        }
+    }
+}
+
+fn trigger_traps(
+    mut entered: MessageReader<ZoneEntered>,
+    zones: Query<&TriggerZone>,
+    mut players: Query<&mut PlayerData, With<Player>>,
+    mut monsters: Query<&mut MonsterAI, With<Monster>>,
+    mut knockback: MessageWriter<ApplyKnockback>,
+) {
+    for ev in entered.read() {
+        let Ok(zone) = zones.get(ev.zone) else {
+            continue;
+        };
+        let ZoneEffect::Trap { damage, stun } = zone.effect else {
+            continue;
+        };
+        if let Ok(mut pdata) = players.get_mut(ev.entity) {
+            pdata.damage(damage);
+            pdata.can_heal.reset();
+        } else if let Ok(mut ai) = monsters.get_mut(ev.entity) {
+            ai.health -= damage;
+        } else {
+            continue;
+        }
+        knockback.write(ApplyKnockback { target: ev.entity, direction: Vec2::ZERO, impulse: 0.0, stun });
+    }
+}
+
+fn discover_structures(
+    mut entered: MessageReader<ZoneEntered>,
+    zones: Query<(&TriggerZone, &Transform)>,
+    players: Query<(), With<Player>>,
+    mut discoveries: ResMut<Discoveries>,
+) {
+    for ev in entered.read() {
+        if players.get(ev.entity).is_err() {
+            continue;
+        }
+        let Ok((zone, transform)) = zones.get(ev.zone) else {
+            continue;
+        };
+        let ZoneEffect::Discovery { name } = &zone.effect else {
+            continue;
+        };
+        if discoveries.0.insert(TerrainMap::tile_at(transform.translation.xy())) {
+            info!("Odkryto: {}", name);
+        }
+    }
+}
+
+/// Nagrodę ze strefy gracz bierze akcją Interact (tylko jeśli jeszcze jej nie ma)
+fn grant_rewards(
+    actions: Res<ActionState>,
+    zones: Query<(&TriggerZone, &ZoneOccupants)>,
+    config: Res<ItemConfig>,
+    mut players: Query<&mut PlayerData, With<Player>>,
+) {
+    if !actions.just_pressed(Action::Interact) {
+        return;
+    }
+    for (zone, occupants) in &zones {
+        let ZoneEffect::Reward { item } = &zone.effect else {
+            continue;
+        };
+        for &entity in &occupants.0 {
+            let Ok(mut pdata) = players.get_mut(entity) else {
+                continue;
+            };
+            if pdata.inventory.contains(item) {
+                continue;
+            }
+            let Some(template) = config.items.get(item) else {
+                warn!("Nieznany przedmiot nagrody: {}", item);
+                continue;
+            };
+            if pdata.inventory.add_to_free_slot(template.clone()) {
+                info!("Znaleziono: {}", item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::actions::ActionsPlugin;
+    use crate::systems::settings::Settings;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::fs;
+
+    fn reward_app() -> (App, Entity) {
+        let mut app = App::new();
+        let config: ItemConfig =
+            serde_json::from_str(&fs::read_to_string("assets/config/items.json").unwrap()).unwrap();
+        app.add_plugins(ActionsPlugin)
+            .insert_resource(config)
+            .insert_resource(Settings::default())
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_systems(Update, grant_rewards);
+        let player = app.world_mut()
+            .run_system_once(|mut commands: Commands, config: Res<ItemConfig>| {
+                commands.spawn((Player, PlayerData::new(&config))).id()
+            })
+            .unwrap();
+        app.world_mut().spawn((
+            TriggerZone { effect: ZoneEffect::Reward { item: "amulet_ward".to_string() } },
+            ZoneOccupants([player].into()),
+        ));
+        (app, player)
+    }
+
+    fn amulets(app: &mut App, player: Entity) -> usize {
+        let pdata = app.world().get::<PlayerData>(player).unwrap();
+        pdata.inventory.items.values().filter(|item| item.id == "amulet_ward").count()
+    }
+
+    fn interact(app: &mut App) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyE);
+        app.update();
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::KeyE);
+        app.update();
+    }
+
+    #[test]
+    fn reward_needs_interact_and_is_given_once() {
+        let (mut app, player) = reward_app();
+        app.update();
+        assert_eq!(amulets(&mut app, player), 0);
+        for _ in 0..2 {
+            interact(&mut app);
+            assert_eq!(amulets(&mut app, player), 1);
+        }
+    }
+}
@@ -1,17 +1,16 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use crate::resourses::physics_resources::*;
 
 use noise::{NoiseFn, Fbm, Perlin};
 use std::collections::HashSet;
 
-use rapier2d::prelude::ImpulseJointSet;
-use rapier2d::prelude::MultibodyJointSet;
-
 use bevy_2d_screen_space_lightmaps::lightmap_plugin::lightmap_plugin::*;
 use bevy::camera::visibility::RenderLayers;
 
 use std::collections::HashMap;
 use bevy_firefly::prelude::*;
+use crate::systems::tile_sim::SimCell;
 
 #[derive(Component, Clone)]
 pub struct GapOccluder;
@@ -19,6 +18,10 @@ pub struct GapOccluder;
 const TR_LOCAL: Vec2 = Vec2::new(-TILE_SIZE / 9.0, TILE_SIZE / 1.75);
 const HALF_TILE: Vec2 = Vec2::new(TILE_SIZE / 1.125, TILE_SIZE / 1.125);
 
+// progi noise wysokości dla kolejnych poziomów (poziom = liczba przekroczonych progów)
+const ELEVATION_THRESHOLDS: [f64; MAX_ELEVATION as usize] = [-0.15, 0.2];
+const RAMP_THRESHOLD: f64 = 0.3;
+
 #[derive(Component, Clone)]
 pub struct OccluderMeta {
     /// lokalny transform taki jaki miał occluder na starcie (base)
@@ -27,15 +30,67 @@ pub struct OccluderMeta {
     pub base_half: Vec2,
 }
 
+/// Zmiana wyglądu wygenerowanych kafelków podłogi (symulacja, pory roku)
+#[derive(SystemParam)]
+pub struct FloorSprites<'w, 's> {
+    floors: Query<'w, 's, &'static Children, With<Floor>>,
+    sprites: Query<'w, 's, &'static mut Sprite, With<FloorSprite>>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl FloorSprites<'_, '_> {
+    pub fn set_look(&mut self, floor: Entity, (texture, color): (&'static str, Color)) {
+        let Ok(children) = self.floors.get(floor) else {
+            return;
+        };
+        let Some(child) = children.iter().find(|child| self.sprites.contains(*child)) else {
+            return;
+        };
+        if let Ok(mut sprite) = self.sprites.get_mut(child) {
+            sprite.image = self.asset_server.load(texture);
+            sprite.color = color;
+        }
+    }
+}
+
 #[derive(Resource, Default)]
-struct TerrainMap {
+pub struct TerrainMap {
     generated: HashSet<IVec2>,
     linked: HashSet<IVec2, Vec<Entity>>,
     wall_map: HashMap<IVec2, Entity>,
+    biomes: HashMap<IVec2, Biome>,
+    pub hazards: HashMap<IVec2, HazardKind>,
+    pub floors: HashMap<IVec2, Entity>,
+    /// podłoga bez zagrożeń, ścian i klifów (woda nie jest podłogą)
+    pub walkable: HashSet<IVec2>,
+    pub sim_cells: HashMap<IVec2, SimCell>,
     pub gap_occluders: HashMap<(Entity, Entity), Entity>,
 }
 
 impl TerrainMap {
+    /// Klucz kafelka (środek w pikselach) dla dowolnej pozycji w świecie
+    pub fn tile_at(world_pos: Vec2) -> IVec2 {
+        IVec2::new(
+            (world_pos.x / TILE_SIZE).round() as i32 * TILE_SIZE as i32,
+            (world_pos.y / TILE_SIZE).round() as i32 * TILE_SIZE as i32,
+        )
+    }
+
+    /// Biom kafelka pod podaną pozycją (None, jeśli teren nie jest wygenerowany)
+    pub fn biome_at(&self, world_pos: Vec2) -> Option<Biome> {
+        self.biomes.get(&Self::tile_at(world_pos)).copied()
+    }
+
+    /// Czy kafelek (klucz jak w generated) jest wygenerowany
+    pub fn is_generated(&self, tile: IVec2) -> bool {
+        self.generated.contains(&tile)
+    }
+
+    /// Niebezpieczny kafelek pod podaną pozycją
+    pub fn hazard_at(&self, world_pos: Vec2) -> Option<HazardKind> {
+        self.hazards.get(&Self::tile_at(world_pos)).copied()
+    }
+
     /// Tworzy canonical key (A,B) niezależnie od kolejności
     fn canonical_pair(a: Entity, b: Entity) -> (Entity, Entity) {
         if a.index() < b.index() { (a,b) } else { (b,a) }
@@ -183,7 +238,8 @@ impl Plugin for TerrainGenerationPlugin {
     fn build(&self, app: &mut App) {
          app.insert_resource(TerrainMap::default())
             .add_systems(Startup, init_terrain)
-            .add_systems(Update, (update_terrain, animate_sprite, y_sort_relative));
+            .add_systems(Update, (update_terrain, animate_sprite, apply_hazards).run_if(in_state(AppState::Playing)))
+            .add_systems(Update, y_sort_relative.run_if(in_state(AppState::Playing).or(in_state(AppState::Paused))));
     }
 }
 
@@ -224,6 +280,9 @@ fn init_terrain(
     mut terrain_map: ResMut<TerrainMap>,
     query_non_phys: Query<(Entity, &Transform, Option<&Fog>, &Children), (Or<(With<Floor>, With<Wall>)>, Without<RigidBodyHandleComponent>)>,
     mut sprite_query: Query<&mut Sprite, With<WaterSprite>>,
+    world_diff: Res<WorldDiff>,
+    weather: Res<Weather>,
+    world_seed: Res<WorldSeed>,
 ) {
     let center = IVec2::ZERO;
     generate_area(
@@ -235,6 +294,9 @@ fn init_terrain(
         center,
         &query_non_phys,
         &mut sprite_query,
+        &world_diff,
+        &weather,
+        &world_seed,
     );
     generate_halo(
         &mut commands,
@@ -253,13 +315,12 @@ fn update_terrain(
     mut terrain_map: ResMut<TerrainMap>,
     player_q: Query<&Transform, With<Player>>,
     mut sprite_query: Query<&mut Sprite, With<WaterSprite>>,
-    // do usuwania fizyki
-    mut colliders: ResMut<ResColliderSet>,
-    mut rigid_bodies: ResMut<ResRigidBodySet>,
-    mut island_manager: ResMut<ResIslandManager>,
-    query_phys: Query<(Entity, &Transform, &RigidBodyHandleComponent), Or<(With<Floor>, With<Wall>)>>,
+    query_phys: Query<(Entity, &Transform), (Or<(With<Floor>, With<Wall>)>, With<RigidBodyHandleComponent>)>,
     query_non_phys: Query<(Entity, &Transform, Option<&Fog>, &Children), (Or<(With<Floor>, With<Wall>)>, Without<RigidBodyHandleComponent>)>,
     mut halo_query: Query<&mut Transform, (With<FogHalo>, Without<Floor>, Without<Wall>, Without<Player>)>,
+    world_diff: Res<WorldDiff>,
+    weather: Res<Weather>,
+    world_seed: Res<WorldSeed>,
 ) {
     let player_transform = if let Ok(d) = player_q.single() {
         d
@@ -289,6 +350,9 @@ fn update_terrain(
         center,
         &query_non_phys,
         &mut sprite_query,
+        &world_diff,
+        &weather,
+        &world_seed,
     );
 
     // === Usuń stary teren ===
@@ -305,28 +369,10 @@ fn update_terrain(
 
     for pos in to_remove {
         // usuń encje w tym kafelku
-        for (entity, transform, handle) in query_phys.iter() {
+        for (entity, transform) in query_phys.iter() {
             if !(transform.translation.x as i32 == pos.x && transform.translation.y as i32 == pos.y) {
                 continue;
             }
-            let mut colliders_clone = Vec::new();
-            if let Some(rb) = rigid_bodies.0.get(handle.0) {
-                for collider_handle in rb.colliders() {
-                    colliders_clone.push(collider_handle.clone());
-                }
-            }
-
-            for collider_handle in colliders_clone {
-                colliders.0.remove(collider_handle, &mut island_manager.0, &mut rigid_bodies.0, true);
-            }
-            rigid_bodies.0.remove(
-                handle.0,
-                &mut island_manager.0,
-                &mut colliders.0,
-                &mut ImpulseJointSet::new(),
-                &mut MultibodyJointSet::new(),
-                true, // usuwa powiązane collidery
-            );
             terrain_map.remove_gaps_for_wall(entity, &mut commands);
             terrain_map.wall_map.retain(|_, &mut e| e != entity);
             commands.entity(entity).despawn();
@@ -340,6 +386,11 @@ fn update_terrain(
         }
 
         terrain_map.generated.remove(&pos);
+        terrain_map.biomes.remove(&pos);
+        terrain_map.hazards.remove(&pos);
+        terrain_map.floors.remove(&pos);
+        terrain_map.walkable.remove(&pos);
+        terrain_map.sim_cells.remove(&pos);
     }
 }
 
@@ -352,10 +403,15 @@ fn generate_area(
     center: IVec2,
     query_non_phys: &Query<(Entity, &Transform, Option<&Fog>, &Children), (Or<(With<Floor>, With<Wall>)>, Without<RigidBodyHandleComponent>)>,
     sprite_query: &mut Query<&mut Sprite, With<WaterSprite>>,
+    world_diff: &WorldDiff,
+    weather: &Weather,
+    world_seed: &WorldSeed,
 ) {
-    let terrain_noise = Fbm::<Perlin>::new(921925);
-    let path_noise = Fbm::<Perlin>::new(5342756);
-    let biome_noise = Fbm::<Perlin>::new(2683467); // nowy noise dla biomów
+    let terrain_noise = Fbm::<Perlin>::new(world_seed.noise_seed(921925));
+    let path_noise = Fbm::<Perlin>::new(world_seed.noise_seed(5342756));
+    let biome_noise = Fbm::<Perlin>::new(world_seed.noise_seed(2683467)); // nowy noise dla biomów
+    let elevation_noise = Fbm::<Perlin>::new(world_seed.noise_seed(7351093));
+    let ramp_noise = Fbm::<Perlin>::new(world_seed.noise_seed(4419283));
 
     let world_size_x = WORLD_SIZE/3;
     let world_size_y = WORLD_SIZE/3;
@@ -445,50 +501,19 @@ fn generate_area(
             terrain_map.generated.insert(pos);
             // === wybór biomu ===
             let biome_val = biome_noise.get([(x / tile_size) as f64 / 128.0, (y / tile_size) as f64 / 128.0]);
-            let biome = "normal";
+            let biome = biome_for_noise(biome_val);
+            terrain_map.biomes.insert(pos, biome);
 
             // === noise terenu w obrębie biomu ===
             let terrain_val = terrain_noise.get([(x / tile_size) as f64 / 15.0, (y / tile_size) as f64 / 15.0]);
 
-            let mut texture_path = match biome {
-                "snow" => {
-                    if terrain_val < -0.45 {
-                        "textures/water"
-                    } else if terrain_val < -0.25 {
-                        "textures/ice.png"
-                    } else {
-                        "textures/snow.png"
-                    }
-                }
-                "evil" => {
-                    if terrain_val < -0.45 {
-                        "textures/water"
-                    } else if terrain_val < -0.25 {
-                        "textures/evil_dirt.png"
-                    } else if terrain_val < 0.3 {
-                        "textures/evil_grass.png"
-                    } else {
-                        "textures/evil_stone.png"
-                    }
-                }
-                _ => {
-                    // normal
-                    if terrain_val < -0.45 {
-                        "textures/water"
-                    } else if terrain_val < -0.25 {
-                        "textures/sand.png"
-                    } else if terrain_val < 0.0 {
-                        "textures/dirt.png"
-                    } else if terrain_val < 0.3 {
-                        "textures/grass.png"
-                    } else {
-                        "textures/stone.png"
-                    }
-                }
-            };
+            let rule = floor_rule(biome, terrain_val);
+            let mut texture_path = rule.texture;
+            let mut hazard = rule.hazard;
 
             // path tylko w normal i evil
-            if biome != "snow"
+            if biome != Biome::Snow
+                && hazard.is_none()
                 && (texture_path.ends_with("dirt.png") || texture_path.ends_with("grass.png"))
             {
                 let path_val = path_noise.get([gx as f64 / 8.0, gy as f64 / 8.0]);
@@ -497,12 +522,35 @@ fn generate_area(
                 }
             }
 
+            // === poziom terenu ===
+            let elevation = tile_elevation(&elevation_noise, x, y);
+            let is_water = texture_path == "textures/water";
+            let is_edge = !is_water && is_cliff_edge(&elevation_noise, x, y, elevation);
+            let is_ramp = is_edge
+                && ramp_noise.get([(x / tile_size) as f64 / 5.0, (y / tile_size) as f64 / 5.0]) > RAMP_THRESHOLD;
+            if is_ramp {
+                texture_path = "textures/path.png";
+                hazard = None;
+            }
+            if let Some(kind) = hazard {
+                terrain_map.hazards.insert(pos, kind);
+            }
+
+            // === Ściany tylko na stone/evil_stone ===
+            let has_wall = hazard.is_none()
+                && (texture_path == "textures/stone.png" || texture_path == "textures/evil_stone.png")
+                && terrain_noise.get([(x / tile_size) as f64 / 6.0, (y / tile_size) as f64 / 6.0, 999.0]) > 0.0;
+
             // === Spawn Floor ===
-            if texture_path == "textures/water" {
+            if is_water {
                 // woda animowana
                 let entity =commands.spawn((
                     Floor,
+                    Water,
                     Pending,
+                    PhysicsBody::Fixed,
+                    BodyShape::Cuboid { half_extents: Vec2::splat(tile_size / 2.0) },
+                    CollisionLayer::Water,
                     Mesh2d(meshes.add(Rectangle::new(tile_size, tile_size))),
                     //Transform::from_xyz(x, y, -3.0 + -(g_offset/64.0 + y/64.0)+64.0),
                     Transform::from_xyz(x, y, -64.0),
@@ -523,16 +571,47 @@ fn generate_area(
                         AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
                         WaterSprite,
                         RenderLayers::from_layers(CAMERA_LAYER_FLOOR)
+                    ),(
+                        // lód pokazywany zimą (seasons::freeze_water)
+                        Sprite::from_image(asset_server.load("textures/ice.png")),
+                        Transform {
+                            scale: Vec3::new(tile_size / 32.0, tile_size / 32.0, 1.0),
+                            ..Default::default()
+                        },
+                        YSort { z: 0.01 },
+                        Visibility::Hidden,
+                        IceSprite,
+                        RenderLayers::from_layers(CAMERA_LAYER_FLOOR)
                     )],
                 ));
             } else {
-                commands.spawn((
+                let mut floor_color = elevation_tint(elevation);
+                if let Some(kind) = hazard {
+                    floor_color = kind.tint().mix(&floor_color, 0.35);
+                }
+                let mut floor_texture = texture_path;
+                // trawa bierze udział w symulacji (ogień, odrastanie, śnieg)
+                if hazard.is_none() && texture_path.ends_with("grass.png") {
+                    let cell = SimCell {
+                        base_texture: texture_path,
+                        base_color: floor_color,
+                        state: world_diff.tiles.get(&pos).copied().unwrap_or(TileState::Grass),
+                    };
+                    (floor_texture, floor_color) = cell.look(weather);
+                    terrain_map.sim_cells.insert(pos, cell);
+                }
+                let mut floor = commands.spawn((
                     Floor,
                     Mesh2d(meshes.add(Rectangle::new(tile_size, tile_size))),
                     //Transform::from_xyz(x, y, -3.0 + -(g_offset/64.0 + y/64.0)+64.0),
                     Transform::from_xyz(x, y, -64.0),
                     children![(
-                        Sprite::from_image(asset_server.load(texture_path)),
+                        {
+                            let mut s = Sprite::from_image(asset_server.load(floor_texture));
+                            s.color = floor_color;
+                            s
+                        },
+                        FloorSprite,
                         YSort { z: 0.0 },
                         Transform {
                             scale: Vec3::new(tile_size / 32.0, tile_size / 32.0, 1.0),
@@ -541,16 +620,57 @@ fn generate_area(
                         RenderLayers::from_layers(CAMERA_LAYER_FLOOR)
                     )],
                 ));
+                terrain_map.floors.insert(pos, floor.id());
+                if hazard.is_none() && !has_wall && (!is_edge || is_ramp) {
+                    terrain_map.walkable.insert(pos);
+                }
+                // lawa i bagno świecą
+                if let Some(kind) = hazard && kind != HazardKind::Thorns {
+                    floor.with_child((
+                        Transform::from_xyz(0.0, 0.0, 0.0),
+                        LightSource {
+                            range: 96.0,
+                            intensity: if kind == HazardKind::Lava { 0.2 } else { 0.05 },
+                            color: kind.tint(),
+                        },
+                        YSort { z: 0.0 },
+                    ));
+                }
+                // rampa (bez kolizji) to jedyne przejście między poziomami terenu
+                if is_edge && !is_ramp && !has_wall {
+                    // klif: kolizja + ściana klifu rzucająca cień
+                    floor.insert((
+                        Cliff,
+                        Pending,
+                        PhysicsBody::Fixed,
+                        BodyShape::Cuboid { half_extents: Vec2::splat(tile_size / 2.0) },
+                        CollisionLayer::Wall,
+                    ));
+                    floor.with_children(|parent| {
+                        parent.spawn((
+                            Transform::from_xyz(TR_LOCAL.x, TR_LOCAL.y, 0.0),
+                            Occluder2d::rectangle(HALF_TILE.x, HALF_TILE.y),
+                            YSort { z: 0.8 },
+                        ));
+                        parent.spawn((
+                            RenderLayers::from_layers(CAMERA_LAYER_WALL),
+                            YSort { z: 0.3 },
+                            {
+                                let mut s = Sprite::from_image(asset_server.load("textures/wall_main.png"));
+                                s.color = Color::srgb(0.55, 0.45, 0.35).mix(&elevation_tint(elevation), 0.5);
+                                s
+                            },
+                            Transform::from_xyz(0.0, 0.0, 0.0)
+                                .with_scale(Vec3::new(tile_size / 32.0, tile_size / 32.0, 1.0)),
+                        ));
+                    });
+                }
             }
 
-            // === Ściany tylko na stone/evil_stone ===
-            if texture_path == "textures/stone.png" || texture_path == "textures/evil_stone.png" {
-                let wall_val = terrain_noise.get([(x / tile_size) as f64 / 6.0, (y / tile_size) as f64 / 6.0, 999.0]);
-                if wall_val > 0.0 {
-                    let wall_entity = spawn_wall(commands, meshes, asset_server, x, y, tile_size, g_offset);
-                    terrain_map.wall_map.insert(pos, wall_entity);
-                    add_gap_occluders_for_tile(commands, terrain_map, pos, tile_size);
-                }
+            if has_wall {
+                let wall_entity = spawn_wall(commands, meshes, asset_server, x, y, tile_size, g_offset);
+                terrain_map.wall_map.insert(pos, wall_entity);
+                add_gap_occluders_for_tile(commands, terrain_map, pos, tile_size);
             }
 
             if dist2*dist2 >= 0.0 {
@@ -607,6 +727,78 @@ fn generate_area(
     }
 }
 
+/// Wiersz tabeli generacji podłogi: kafelek dla terrain_val < `below`
+struct FloorRule {
+    below: f64,
+    texture: &'static str,
+    hazard: Option<HazardKind>,
+}
+
+const NORMAL_FLOOR: &[FloorRule] = &[
+    FloorRule { below: -0.45, texture: "textures/water", hazard: None },
+    FloorRule { below: -0.25, texture: "textures/sand.png", hazard: None },
+    FloorRule { below: 0.0, texture: "textures/dirt.png", hazard: None },
+    FloorRule { below: 0.25, texture: "textures/grass.png", hazard: None },
+    FloorRule { below: 0.3, texture: "textures/grass.png", hazard: Some(HazardKind::Thorns) },
+    FloorRule { below: f64::INFINITY, texture: "textures/stone.png", hazard: None },
+];
+
+const SNOW_FLOOR: &[FloorRule] = &[
+    FloorRule { below: -0.45, texture: "textures/water", hazard: None },
+    FloorRule { below: -0.25, texture: "textures/ice.png", hazard: None },
+    FloorRule { below: f64::INFINITY, texture: "textures/snow.png", hazard: None },
+];
+
+const EVIL_FLOOR: &[FloorRule] = &[
+    FloorRule { below: -0.45, texture: "textures/water", hazard: None },
+    FloorRule { below: -0.35, texture: "textures/evil_grass.png", hazard: Some(HazardKind::PoisonSwamp) },
+    FloorRule { below: -0.25, texture: "textures/evil_dirt.png", hazard: None },
+    FloorRule { below: 0.3, texture: "textures/evil_grass.png", hazard: None },
+    FloorRule { below: 0.6, texture: "textures/evil_stone.png", hazard: None },
+    FloorRule { below: f64::INFINITY, texture: "textures/evil_stone.png", hazard: Some(HazardKind::Lava) },
+];
+
+/// Wybiera wiersz tabeli generacji dla biomu i wartości noise terenu
+fn floor_rule(biome: Biome, terrain_val: f64) -> &'static FloorRule {
+    let table = match biome {
+        Biome::Normal => NORMAL_FLOOR,
+        Biome::Snow => SNOW_FLOOR,
+        Biome::Evil => EVIL_FLOOR,
+    };
+    table.iter().find(|rule| terrain_val < rule.below).unwrap_or(&table[table.len() - 1])
+}
+
+/// Wybór biomu na podstawie wartości noise biomów
+fn biome_for_noise(biome_val: f64) -> Biome {
+    if biome_val < -0.25 {
+        Biome::Snow
+    } else if biome_val > 0.3 {
+        Biome::Evil
+    } else {
+        Biome::Normal
+    }
+}
+
+/// Poziom wysokości kafelka wyliczony z noise (0..=MAX_ELEVATION)
+fn tile_elevation(elevation_noise: &Fbm<Perlin>, x: f32, y: f32) -> i32 {
+    let val = elevation_noise.get([(x / TILE_SIZE) as f64 / 40.0, (y / TILE_SIZE) as f64 / 40.0]);
+    ELEVATION_THRESHOLDS.iter().filter(|&&t| val >= t).count() as i32
+}
+
+/// Kafelek jest krawędzią klifu, jeśli któryś z sąsiadów leży niżej
+fn is_cliff_edge(elevation_noise: &Fbm<Perlin>, x: f32, y: f32, elevation: i32) -> bool {
+    let dirs = [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y];
+    dirs.iter().any(|dir| {
+        tile_elevation(elevation_noise, x + dir.x * TILE_SIZE, y + dir.y * TILE_SIZE) < elevation
+    })
+}
+
+/// Wyższe poziomy są jaśniejsze, żeby było widać ukształtowanie terenu
+fn elevation_tint(elevation: i32) -> Color {
+    let l = 0.8 + 0.1 * elevation as f32;
+    Color::srgb(l, l, l)
+}
+
 fn spawn_wall(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
@@ -621,6 +813,9 @@ fn spawn_wall(
     return commands.spawn((
         Wall,
         Pending,
+        PhysicsBody::Fixed,
+        BodyShape::Cuboid { half_extents: Vec2::splat(tile_size / 2.0) },
+        CollisionLayer::Wall,
         Mesh2d(meshes.add(Rectangle::new(tile_size, tile_size))),
         Transform::from_xyz(x, y, -32.0),
         children![(
@@ -719,6 +914,55 @@ fn generate_halo(
     });
 }
 
+/// Obrażenia od niebezpiecznych kafelków dla gracza i potworów + podświetlenie sprite'a
+fn apply_hazards(
+    time: Res<Time>,
+    terrain_map: Res<TerrainMap>,
+    mut player_query: Query<(&Transform, &mut PlayerData), (With<Player>, Without<Pending>)>,
+    mut monster_query: Query<(&Transform, &mut MonsterAI), (With<Monster>, Without<Player>, Without<Pending>)>,
+    parents: Query<&Transform, Or<(With<Player>, With<Monster>)>>,
+    mut sprites: Query<(&ChildOf, &mut Sprite, &mut HazardTint)>,
+) {
+    let dt = time.delta_secs();
+    if let Ok((transform, mut player_data)) = player_query.single_mut()
+        && let Some(kind) = terrain_map.hazard_at(transform.translation.xy())
+    {
+        player_data.damage(kind.damage_per_second() * dt);
+        player_data.can_heal.reset();
+    }
+    for (transform, mut ai) in &mut monster_query {
+        if let Some(kind) = terrain_map.hazard_at(transform.translation.xy()) {
+            ai.health -= kind.damage_per_second() * dt;
+        }
+    }
+
+    for (child_of, mut sprite, mut tint) in &mut sprites {
+        let Ok(transform) = parents.get(child_of.parent()) else {
+            continue;
+        };
+        let kind = terrain_map.hazard_at(transform.translation.xy());
+        if tint.0 != kind {
+            tint.0 = kind;
+            sprite.color = kind.map_or(Color::WHITE, HazardKind::tint);
+        }
+    }
+}
+
+/// Zmienia kierunek ruchu tak, żeby ominąć niebezpieczny kafelek przed encją (jeśli się da)
+pub fn avoid_hazards(terrain_map: &TerrainMap, pos: Vec2, dir: Vec2) -> Vec2 {
+    if dir == Vec2::ZERO || terrain_map.hazard_at(pos).is_some() {
+        return dir;
+    }
+    let look_ahead = TILE_SIZE * 0.75;
+    for angle in [0.0f32, 45.0, -45.0, 90.0, -90.0] {
+        let candidate = Vec2::from_angle(angle.to_radians()).rotate(dir);
+        if terrain_map.hazard_at(pos + candidate * look_ahead).is_none() {
+            return candidate;
+        }
+    }
+    dir
+}
+
 fn animate_sprite(
     time: Res<Time>,
     mut query: Query<(&AnimationIndices, &mut AnimationTimer, &mut Sprite, &mut Transform), With<WaterSprite>>,
@@ -0,0 +1,303 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use crate::resourses::physics_resources::*;
+use crate::systems::terrain::{FloorSprites, TerrainMap};
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub struct TileSimPlugin;
+
+pub const SIM_CHUNK_TILES: i32 = 8; // bok chunku symulacji w kafelkach
+pub const SIM_SALT: u64 = 0x5EED_71E5;
+
+const BURN_TICKS: u32 = 8;
+const REGROW_TICKS: u32 = 240;
+const SPREAD_CHANCE: f32 = 0.3;
+const LAVA_IGNITION_CHANCE: f32 = 0.02;
+const IGNITION_CHANCE: f32 = 0.0005; // na chunk na tick
+const SNOW_CHANCE: f32 = 0.02;
+const MELT_CHANCE: f32 = 0.05;
+
+/// Kafelek biorący udział w symulacji: wygląd z generacji + aktualny stan
+pub struct SimCell {
+    pub base_texture: &'static str,
+    pub base_color: Color,
+    pub state: TileState,
+}
+
+impl SimCell {
+    /// Tekstura i kolor sprite'a dla aktualnego stanu (trawa w palecie pory roku)
+    pub fn look(&self, weather: &Weather) -> (&'static str, Color) {
+        match self.state {
+            TileState::Grass => {
+                let base = self.base_color.to_srgba();
+                let tint = weather.grass_tint.to_srgba();
+                (self.base_texture, Color::srgb(base.red * tint.red, base.green * tint.green, base.blue * tint.blue))
+            }
+            TileState::Burning { .. } => (self.base_texture, Color::srgb(1.0, 0.45, 0.15)),
+            TileState::Burned { .. } => ("textures/dirt.png", Color::srgb(0.35, 0.3, 0.28)),
+            TileState::Snowy => ("textures/snow.png", self.base_color),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct TileSimClock {
+    pub tick: u64,
+}
+
+impl Plugin for TileSimPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TileSimClock::default())
+            .insert_resource(WorldDiff::default())
+            .init_resource::<WorldSeed>()
+            .insert_resource(Weather::default())
+            .add_systems(FixedUpdate, simulate_tiles
+                .run_if(in_state(AppState::Playing))
+                .run_if(on_timer(Duration::from_millis(500))));
+    }
+}
+
+fn simulate_tiles(
+    mut clock: ResMut<TileSimClock>,
+    mut terrain_map: ResMut<TerrainMap>,
+    mut world_diff: ResMut<WorldDiff>,
+    weather: Res<Weather>,
+    world_seed: Res<WorldSeed>,
+    mut floor_sprites: FloorSprites,
+) {
+    clock.tick += 1;
+    let changes = step_cells(&terrain_map, weather.cold, world_seed.sim_seed(SIM_SALT), clock.tick);
+
+    for (pos, state) in changes {
+        let Some(cell) = terrain_map.sim_cells.get_mut(&pos) else {
+            continue;
+        };
+        cell.state = state;
+        // do diffu trafiają tylko kafelki różne od wygenerowanych
+        if state == TileState::Grass {
+            world_diff.tiles.remove(&pos);
+        } else {
+            world_diff.tiles.insert(pos, state);
+        }
+
+        let look = cell.look(&weather);
+        if let Some(&floor) = terrain_map.floors.get(&pos) {
+            floor_sprites.set_look(floor, look);
+        }
+    }
+}
+
+/// Jeden krok symulacji. Wynik zależy tylko od stanu, seeda i numeru ticku
+pub fn step_cells(terrain_map: &TerrainMap, cold: bool, seed: u64, tick: u64) -> Vec<(IVec2, TileState)> {
+    let cells = &terrain_map.sim_cells;
+    let tile = TILE_SIZE as i32;
+    let dirs = [IVec2::new(tile, 0), IVec2::new(-tile, 0), IVec2::new(0, tile), IVec2::new(0, -tile)];
+
+    let mut chunks: HashMap<IVec2, Vec<IVec2>> = HashMap::new();
+    for &pos in cells.keys() {
+        chunks.entry(chunk_of(pos)).or_default().push(pos);
+    }
+
+    let mut next: HashMap<IVec2, TileState> = HashMap::new();
+    for (&chunk, positions) in chunks.iter_mut() {
+        positions.sort_by_key(|p| (p.x, p.y));
+
+        // spontaniczny zapłon - jeden los na chunk
+        if !cold && roll(seed, chunk, tick, 0) < IGNITION_CHANCE {
+            let pos = positions[(hash(seed, chunk, tick, 1) % positions.len() as u64) as usize];
+            if cells[&pos].state == TileState::Grass {
+                next.insert(pos, TileState::Burning { ticks: 0 });
+            }
+        }
+
+        // spokojne chunki (sama trawa, ciepło) nie mają nic do roboty
+        let active = cold || positions.iter().any(|p| cells[p].state != TileState::Grass);
+        if !active {
+            continue;
+        }
+
+        for &pos in positions.iter() {
+            match cells[&pos].state {
+                TileState::Burning { ticks } => {
+                    let state = if ticks + 1 >= BURN_TICKS {
+                        TileState::Burned { ticks: 0 }
+                    } else {
+                        TileState::Burning { ticks: ticks + 1 }
+                    };
+                    next.insert(pos, state);
+                    // ogień przechodzi na sąsiednią trawę (także w innych chunkach)
+                    for dir in dirs {
+                        let npos = pos + dir;
+                        if let Some(neighbor) = cells.get(&npos)
+                            && neighbor.state == TileState::Grass
+                            && roll(seed, npos, tick, 2) < SPREAD_CHANCE
+                        {
+                            next.insert(npos, TileState::Burning { ticks: 0 });
+                        }
+                    }
+                }
+                TileState::Burned { ticks } => {
+                    let state = if ticks + 1 >= REGROW_TICKS {
+                        TileState::Grass
+                    } else {
+                        TileState::Burned { ticks: ticks + 1 }
+                    };
+                    next.insert(pos, state);
+                }
+                TileState::Snowy => {
+                    if !cold && roll(seed, pos, tick, 3) < MELT_CHANCE {
+                        next.entry(pos).or_insert(TileState::Grass);
+                    }
+                }
+                TileState::Grass => {
+                    if cold && roll(seed, pos, tick, 3) < SNOW_CHANCE {
+                        next.entry(pos).or_insert(TileState::Snowy);
+                    }
+                }
+            }
+        }
+    }
+
+    // lawa podpala trawę obok
+    for (&pos, &kind) in terrain_map.hazards.iter() {
+        if kind != HazardKind::Lava {
+            continue;
+        }
+        for dir in dirs {
+            let npos = pos + dir;
+            if let Some(neighbor) = cells.get(&npos)
+                && neighbor.state == TileState::Grass
+                && roll(seed, npos, tick, 4) < LAVA_IGNITION_CHANCE
+            {
+                next.insert(npos, TileState::Burning { ticks: 0 });
+            }
+        }
+    }
+
+    next.into_iter()
+        .filter(|(pos, state)| cells[pos].state != *state)
+        .collect()
+}
+
+fn chunk_of(pos: IVec2) -> IVec2 {
+    let tile = pos / TILE_SIZE as i32;
+    IVec2::new(tile.x.div_euclid(SIM_CHUNK_TILES), tile.y.div_euclid(SIM_CHUNK_TILES))
+}
+
+/// Deterministyczny hash (splitmix64) zamiast rand - symulacja musi się powtarzać
+fn hash(seed: u64, pos: IVec2, tick: u64, salt: u64) -> u64 {
+    let mut z = seed
+        ^ (pos.x as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (pos.y as u32 as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ tick.wrapping_mul(0x1656_67B1_9E37_79F9)
+        ^ salt.wrapping_mul(0x27D4_EB2F_1656_67C5);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+pub fn roll(seed: u64, pos: IVec2, tick: u64, salt: u64) -> f32 {
+    (hash(seed, pos, tick, salt) >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: u64 = 1234;
+
+    /// Kwadrat size x size kafelków trawy od (0, 0)
+    fn grass_map(size: i32) -> TerrainMap {
+        let mut terrain_map = TerrainMap::default();
+        let tile = TILE_SIZE as i32;
+        for x in 0..size {
+            for y in 0..size {
+                terrain_map.sim_cells.insert(IVec2::new(x * tile, y * tile), SimCell {
+                    base_texture: "textures/grass.png",
+                    base_color: Color::WHITE,
+                    state: TileState::Grass,
+                });
+            }
+        }
+        terrain_map
+    }
+
+    fn set(terrain_map: &mut TerrainMap, pos: IVec2, state: TileState) {
+        terrain_map.sim_cells.get_mut(&pos).unwrap().state = state;
+    }
+
+    fn state(terrain_map: &TerrainMap, pos: IVec2) -> TileState {
+        terrain_map.sim_cells[&pos].state
+    }
+
+    /// Kolejne kroki od ticku 1, tak jak w simulate_tiles
+    fn run(terrain_map: &mut TerrainMap, cold: bool, ticks: u64) {
+        for tick in 1..=ticks {
+            for (pos, state) in step_cells(terrain_map, cold, SEED, tick) {
+                set(terrain_map, pos, state);
+            }
+        }
+    }
+
+    fn sorted(mut changes: Vec<(IVec2, TileState)>) -> Vec<(IVec2, TileState)> {
+        changes.sort_by_key(|(pos, _)| (pos.x, pos.y));
+        changes
+    }
+
+    #[test]
+    fn same_seed_and_tick_give_same_changes() {
+        // dwie osobne mapy - inna kolejność w HashMap nie może zmienić wyniku
+        let make = || {
+            let mut terrain_map = grass_map(16);
+            set(&mut terrain_map, IVec2::new(4, 4) * TILE_SIZE as i32, TileState::Burning { ticks: 0 });
+            set(&mut terrain_map, IVec2::new(12, 3) * TILE_SIZE as i32, TileState::Burned { ticks: 10 });
+            terrain_map
+        };
+        let (first_map, second_map) = (make(), make());
+        for tick in 1..50 {
+            for cold in [false, true] {
+                let first = sorted(step_cells(&first_map, cold, SEED, tick));
+                let second = sorted(step_cells(&second_map, cold, SEED, tick));
+                assert_eq!(first, second);
+            }
+        }
+    }
+
+    #[test]
+    fn fire_spreads_to_grass_neighbour() {
+        let tile = TILE_SIZE as i32;
+        let mut terrain_map = grass_map(3);
+        let center = IVec2::new(tile, tile);
+        set(&mut terrain_map, center, TileState::Burning { ticks: 0 });
+        run(&mut terrain_map, false, BURN_TICKS as u64);
+
+        assert_eq!(state(&terrain_map, center), TileState::Burned { ticks: 0 });
+        let neighbours = [IVec2::new(0, tile), IVec2::new(2 * tile, tile), IVec2::new(tile, 0), IVec2::new(tile, 2 * tile)];
+        assert!(neighbours.iter().any(|&pos| matches!(state(&terrain_map, pos), TileState::Burning { .. } | TileState::Burned { .. })));
+    }
+
+    #[test]
+    fn burned_tile_regrows() {
+        let mut terrain_map = grass_map(1);
+        set(&mut terrain_map, IVec2::ZERO, TileState::Burned { ticks: 0 });
+        run(&mut terrain_map, false, REGROW_TICKS as u64 - 1);
+        assert_eq!(state(&terrain_map, IVec2::ZERO), TileState::Burned { ticks: REGROW_TICKS - 1 });
+        run(&mut terrain_map, false, 1);
+        assert_eq!(state(&terrain_map, IVec2::ZERO), TileState::Grass);
+    }
+
+    #[test]
+    fn snow_falls_only_when_cold() {
+        let snowy = |terrain_map: &TerrainMap| terrain_map.sim_cells.values().filter(|cell| cell.state == TileState::Snowy).count();
+
+        let mut terrain_map = grass_map(8);
+        run(&mut terrain_map, false, 100);
+        assert_eq!(snowy(&terrain_map), 0);
+
+        let mut terrain_map = grass_map(8);
+        run(&mut terrain_map, true, 100);
+        assert!(snowy(&terrain_map) > 0);
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use mgla_2d_game::GamePlugins;
+use mgla_2d_game::resourses::physics_resources::*;
+use mgla_2d_game::systems::autosave::AutosavePlugin;
+use mgla_2d_game::systems::cli::LaunchOptions;
+use mgla_2d_game::systems::headless::{HeadlessPlugin, HeadlessPlugins, SimulationTicks};
+use mgla_2d_game::systems::save::{set_user_data_dir, CurrentSlot};
+use std::sync::Once;
+
+/// limit klatek na wypadek, gdyby gra nigdy nie doszła do Playing
+const MAX_FRAMES: u32 = 10_000;
+
+/// Gra bez okna jak przy --headless: nowy świat z podanym seedem, bez zapisów gracza
+fn headless_app(seed: u64, ticks: Option<u32>) -> App {
+    static DATA_DIR: Once = Once::new();
+    DATA_DIR.call_once(|| set_user_data_dir(std::env::temp_dir().join("mgla_2d_game_tests")));
+
+    let mut app = App::new();
+    app.add_plugins((
+        HeadlessPlugins.set(HeadlessPlugin { ticks }),
+        GamePlugins.build().disable::<AutosavePlugin>(),
+    ))
+    .insert_resource(LaunchOptions { seed: Some(seed), load: None, skip_menu: true });
+    app
+}
+
+/// Klatki aż do `ticks` kroków FixedUpdate w stanie Playing
+fn run_ticks(app: &mut App, ticks: u32) {
+    let target = app.world().resource::<SimulationTicks>().done + ticks;
+    for _ in 0..MAX_FRAMES {
+        if app.world().resource::<SimulationTicks>().done >= target {
+            return;
+        }
+        app.update();
+    }
+    panic!("Symulacja nie wykonała {} kroków", ticks);
+}
+
+fn player_position(app: &mut App) -> Vec2 {
+    let mut query = app.world_mut().query_filtered::<&Transform, With<Player>>();
+    query.single(app.world()).expect("brak gracza").translation.xy()
+}
+
+#[test]
+fn player_walks_with_scripted_input() {
+    let mut app = headless_app(1, None);
+    // teren i ciało gracza powstają w pierwszych krokach
+    run_ticks(&mut app, 10);
+    let start = player_position(&mut app);
+
+    app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+    run_ticks(&mut app, 64);
+    let moved = player_position(&mut app);
+    assert!(moved.x > start.x + 50.0, "gracz nie poszedł w prawo: {} -> {}", start, moved);
+
+    app.world_mut().resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::KeyD);
+    run_ticks(&mut app, 10);
+    let stopped = player_position(&mut app);
+    run_ticks(&mut app, 32);
+    assert_eq!(player_position(&mut app), stopped, "gracz idzie dalej po puszczeniu klawisza");
+}
+
+#[test]
+fn tick_limit_ends_the_run() {
+    let mut app = headless_app(2, Some(30));
+    run_ticks(&mut app, 30);
+    assert_eq!(app.world().resource::<SimulationTicks>().done, 30);
+    assert_eq!(app.should_exit(), Some(AppExit::Success));
+}
+
+#[test]
+fn load_restores_saved_player() {
+    let mut app = headless_app(1, None);
+    run_ticks(&mut app, 10);
+    // z dala od miejsca startu, które dostaje też nowa gra
+    app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+    run_ticks(&mut app, 32);
+    app.world_mut().resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::KeyD);
+    run_ticks(&mut app, 5);
+    let saved = player_position(&mut app);
+    assert!(saved.x > 50.0);
+    app.world_mut().write_message(SaveGame { slot: "test_round_trip".to_string() });
+    run_ticks(&mut app, 2);
+
+    app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyD);
+    run_ticks(&mut app, 32);
+    app.world_mut().resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::KeyD);
+    assert!(player_position(&mut app).distance(saved) > 50.0);
+
+    app.world_mut().write_message(LoadGame { slot: "test_round_trip".to_string(), keep_slot: false });
+    run_ticks(&mut app, 5);
+    let loaded = player_position(&mut app);
+    assert!(loaded.distance(saved) < 1.0, "gracz nie wrócił na zapisane miejsce: {} != {}", loaded, saved);
+}
+
+#[test]
+fn quickload_keeps_current_slot() {
+    let mut app = headless_app(3, None);
+    run_ticks(&mut app, 5);
+    let slot = app.world().resource::<CurrentSlot>().0.clone();
+    app.world_mut().write_message(SaveGame { slot: "test_quickload".to_string() });
+    run_ticks(&mut app, 2);
+
+    app.world_mut().write_message(LoadGame { slot: "test_quickload".to_string(), keep_slot: true });
+    run_ticks(&mut app, 2);
+    assert_eq!(app.world().resource::<CurrentSlot>().0, slot);
+
+    app.world_mut().write_message(LoadGame { slot: "test_quickload".to_string(), keep_slot: false });
+    run_ticks(&mut app, 2);
+    assert_eq!(app.world().resource::<CurrentSlot>().0, "test_quickload");
+}
+
+#[test]
+fn frame_stays_one_tick_after_timestep_change() {
+    let mut app = headless_app(4, None);
+    run_ticks(&mut app, 5);
+    app.world_mut().resource_mut::<PhysicsTimestep>().hz = 30.0;
+    // nowy krok obowiązuje od następnej klatki
+    app.update();
+
+    let before = app.world().resource::<SimulationTicks>().done;
+    for _ in 0..20 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<SimulationTicks>().done, before + 20);
+}